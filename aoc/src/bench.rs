@@ -0,0 +1,176 @@
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use common::input::{InputError, PuzzleInput, Source};
+use common::output::{escape_json, Format};
+use common::run::Failure;
+use common::{diagnostic, Part, Solution};
+
+use crate::days::Day;
+
+/// How long one step took over every iteration.
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    fn from_samples(mut samples: Vec<Duration>) -> Timing {
+        samples.sort();
+
+        let middle = samples.len() / 2;
+        let median = match samples.len() % 2 {
+            0 => (samples[middle - 1] + samples[middle]) / 2,
+            _ => samples[middle],
+        };
+
+        Timing {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Parsing and each part are timed on their own, so a slow parser doesn't hide in a part's time.
+pub struct Measurements {
+    pub parse: Timing,
+    pub parts: Vec<(Part, Timing)>,
+}
+
+struct Report {
+    day: u8,
+    input: PuzzleInput,
+    measurements: Measurements,
+}
+
+/// Times `iterations` runs of parsing and of each part. Everything is run once beforehand,
+/// both to warm up and so that an input the solver fails on is reported instead of timed.
+pub fn measure<S: Solution>(input: &str, iterations: usize) -> Result<Measurements, Failure> {
+    let parsed = S::parse(input).map_err(|err| Box::new(err) as Failure)?;
+    for part in Part::BOTH {
+        S::solve(part, &parsed).map_err(|err| Box::new(err) as Failure)?;
+    }
+
+    let parse = time(iterations, || {
+        black_box(S::parse(black_box(input)).ok());
+    });
+
+    let parts = Part::BOTH
+        .into_iter()
+        .map(|part| {
+            let timing = time(iterations, || {
+                black_box(S::solve(part, black_box(&parsed)).ok());
+            });
+            (part, timing)
+        })
+        .collect();
+
+    Ok(Measurements { parse, parts })
+}
+
+fn time(iterations: usize, mut f: impl FnMut()) -> Timing {
+    let samples = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect();
+
+    Timing::from_samples(samples)
+}
+
+/// Benchmarks each day and prints the results. Days without an input are skipped when
+/// benchmarking every day, the same way `verify` skips them.
+pub fn bench(days: &[Day], input: &Source, iterations: usize, format: Format) -> bool {
+    let skip_missing_inputs = days.len() > 1;
+    let mut reports = Vec::new();
+    let mut succeeded = true;
+
+    for day in days {
+        let input = match input.load(day.number) {
+            Ok(input) => input,
+            Err(InputError::NotFound { .. }) if skip_missing_inputs => continue,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                succeeded = false;
+                continue;
+            }
+        };
+
+        match (day.measure)(&input.text, iterations) {
+            Ok(measurements) => {
+                let report = Report { day: day.number, input, measurements };
+                if format == Format::Text {
+                    print_text(&report);
+                }
+                reports.push(report);
+            }
+            Err(err) => {
+                eprint!("{}", diagnostic::render(&*err, &input.name, &input.text));
+                succeeded = false;
+            }
+        }
+    }
+
+    // JSON is printed all at once so that the output is always a single document
+    if format == Format::Json {
+        println!("{}", to_json(&reports, iterations));
+    }
+
+    succeeded
+}
+
+fn print_text(report: &Report) {
+    println!("Day {}", report.day);
+    print_timing("parse", &report.measurements.parse);
+    for (part, timing) in &report.measurements.parts {
+        print_timing(&format!("part {}", part), timing);
+    }
+}
+
+fn print_timing(label: &str, timing: &Timing) {
+    println!(
+        "  {:<8} min {:>10.1?}   median {:>10.1?}   max {:>10.1?}",
+        label, timing.min, timing.median, timing.max
+    );
+}
+
+// Durations are written in whole nanoseconds so that they can be compared without parsing units
+fn to_json(reports: &[Report], iterations: usize) -> String {
+    let mut json = format!("{{\"iterations\":{},\"days\":[", iterations);
+
+    for (i, report) in reports.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+
+        let _ = write!(
+            json,
+            "{{\"day\":{},\"input\":\"{}\",\"input_checksum\":\"{:016x}\",\"parse\":{}",
+            report.day,
+            escape_json(&report.input.name),
+            report.input.checksum(),
+            timing_json(&report.measurements.parse)
+        );
+        for (part, timing) in &report.measurements.parts {
+            let _ = write!(json, ",\"part{}\":{}", part, timing_json(timing));
+        }
+        json.push('}');
+    }
+
+    json.push_str("]}");
+    json
+}
+
+fn timing_json(timing: &Timing) -> String {
+    format!(
+        "{{\"min_ns\":{},\"median_ns\":{},\"max_ns\":{}}}",
+        timing.min.as_nanos(),
+        timing.median.as_nanos(),
+        timing.max.as_nanos()
+    )
+}
@@ -0,0 +1,63 @@
+//! How much CPU time the current thread has used, for timing days that share the CPUs.
+
+use std::time::Duration;
+
+/// The CPU time the current thread has used so far, or `None` where there's no way to ask.
+#[cfg(all(any(target_os = "linux", target_os = "macos"), target_pointer_width = "64"))]
+pub fn thread_time() -> Option<Duration> {
+    use std::ffi::{c_int, c_long};
+
+    // Both fields are a `long` on the 64-bit platforms this is built for
+    #[repr(C)]
+    struct Timespec {
+        tv_sec: c_long,
+        tv_nsec: c_long,
+    }
+
+    #[cfg(target_os = "linux")]
+    const CLOCK_THREAD_CPUTIME_ID: c_int = 3;
+    #[cfg(target_os = "macos")]
+    const CLOCK_THREAD_CPUTIME_ID: c_int = 16;
+
+    extern "C" {
+        fn clock_gettime(clock: c_int, time: *mut Timespec) -> c_int;
+    }
+
+    let mut time = Timespec { tv_sec: 0, tv_nsec: 0 };
+    // SAFETY: `time` is a `timespec` for `clock_gettime` to fill in, and it's only read after
+    // the call says it succeeded
+    if unsafe { clock_gettime(CLOCK_THREAD_CPUTIME_ID, &mut time) } != 0 {
+        return None;
+    }
+    let seconds = u64::try_from(time.tv_sec).ok()?;
+    let nanoseconds = u32::try_from(time.tv_nsec).ok()?;
+    Some(Duration::new(seconds, nanoseconds))
+}
+
+/// The CPU time the current thread has used so far, or `None` where there's no way to ask.
+#[cfg(not(all(any(target_os = "linux", target_os = "macos"), target_pointer_width = "64")))]
+pub fn thread_time() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thread_time_only_counts_time_spent_working() {
+        let Some(start) = thread_time() else {
+            return;
+        };
+
+        std::thread::sleep(Duration::from_millis(50));
+        let slept = thread_time().unwrap() - start;
+        assert!(slept < Duration::from_millis(25), "sleeping used {:?}", slept);
+
+        let mut sum = 0u64;
+        for i in 0..20_000_000u64 {
+            sum = std::hint::black_box(sum.wrapping_add(i));
+        }
+        assert!(thread_time().unwrap() > start + slept, "{}", sum);
+    }
+}
@@ -0,0 +1,70 @@
+use std::ops::RangeInclusive;
+
+use common::run::{self, Failure, Solved};
+use common::stress::{BruteForce, Generate};
+use common::visual::{self, Visualiser};
+use common::{Part, Solution};
+
+use crate::bench::{self, Measurements};
+
+pub struct Day {
+    pub number: u8,
+    /// Parses the input once and then solves each part in turn.
+    pub solve: fn(&str, &[Part]) -> Result<Solved, Failure>,
+    /// Times parsing and each part over the given number of iterations.
+    pub measure: fn(&str, usize) -> Result<Measurements, Failure>,
+    /// Writes a random input from a seed and a size.
+    pub generate: Generate,
+    /// Solves a generated input the slow way, to check `solve` against.
+    pub brute_force: BruteForce,
+    /// Shows the day being solved step by step, for the days that simulate something.
+    pub visualise: Option<Visualiser>,
+}
+
+macro_rules! day {
+    ($day:ident :: $solution:ident) => {
+        day!(@ $day::$solution, None)
+    };
+    ($day:ident :: $solution:ident, visualise) => {
+        day!(@ $day::$solution, Some(visual::visualise::<$day::$solution>))
+    };
+    (@ $day:ident :: $solution:ident, $visualise:expr) => {
+        Day {
+            number: $day::$solution::DAY,
+            solve: run::solve::<$day::$solution>,
+            measure: bench::measure::<$day::$solution>,
+            generate: $day::stress::generate,
+            brute_force: $day::stress::brute_force,
+            visualise: $visualise,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(day1::Day1),
+    day!(day2::Day2),
+    day!(day3::Day3),
+    day!(day4::Day4),
+    day!(day5::Day5),
+    day!(day6::Day6),
+    day!(day7::Day7),
+    day!(day8::Day8),
+    day!(day9::Day9, visualise),
+    day!(day10::Day10, visualise),
+    day!(day11::Day11),
+    day!(day12::Day12, visualise),
+    day!(day13::Day13),
+    day!(day14::Day14, visualise),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// The days numbered within `range`.
+pub fn select(range: &RangeInclusive<u8>) -> &'static [Day] {
+    // DAYS is in order, so they're all next to each other
+    let first = DAYS.partition_point(|day| day.number < *range.start());
+    let last = DAYS.partition_point(|day| day.number <= *range.end());
+    &DAYS[first..last.max(first)]
+}
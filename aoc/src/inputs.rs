@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use common::input::{self, InputError, Source};
+
+use crate::days;
+
+/// The profile that inputs are imported into when no other is named.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Where a profile's inputs are kept. They're stored as `dayN.txt`, so the directory can be
+/// read like any other directory of inputs.
+pub fn profile_dir(workspace: &Path, profile: &str) -> PathBuf {
+    workspace.join("inputs").join(profile)
+}
+
+/// Profiles become directory names, so they're kept to letters, digits, `-` and `_`.
+pub fn is_valid_profile(profile: &str) -> bool {
+    !profile.is_empty()
+        && profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Copies the input for day `only` from `from` into `store`, or the input of every day that
+/// `from` holds if it's a directory and no day is given. An input that's already there is
+/// only replaced with `force`. Returns whether everything asked for was imported.
+pub fn import(only: Option<u8>, from: &Source, store: &Path, force: bool) -> bool {
+    let mut imported = 0;
+    let mut succeeded = true;
+
+    for day in only.map_or(1..=25, |day| day..=day) {
+        let input = match from.load(day) {
+            Ok(input) => input,
+            // Looking through a whole directory, so days without an input are expected
+            Err(InputError::NotFound { .. }) if only.is_none() => continue,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                succeeded = false;
+                continue;
+            }
+        };
+
+        let path = store.join(format!("day{}.txt", day));
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == input.text => {
+                println!("Day {}: already imported to '{}'", day, path.display());
+                continue;
+            }
+            Ok(_) if !force => {
+                eprintln!(
+                    "error: '{}' already holds a different input for day {}, \
+                     use '--force' to replace it",
+                    path.display(),
+                    day
+                );
+                succeeded = false;
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Err(err) = fs::create_dir_all(store).and_then(|()| fs::write(&path, &input.text)) {
+            eprintln!("error: could not write '{}': {}", path.display(), err);
+            succeeded = false;
+            continue;
+        }
+        println!(
+            "Day {}: imported '{}' to '{}' (checksum {:016x})",
+            day,
+            input.name,
+            path.display(),
+            input.checksum()
+        );
+        imported += 1;
+    }
+
+    if only.is_none() && imported == 0 && succeeded {
+        eprintln!("error: found no inputs to import");
+        return false;
+    }
+    succeeded
+}
+
+/// Prints the checksum of each input in `store`, and which days that have a solver don't
+/// have an input there yet.
+pub fn list(profile: &str, store: &Path) {
+    println!("Inputs for profile '{}' in '{}'", profile, store.display());
+
+    let mut missing = Vec::new();
+    for day in 1..=25 {
+        let path = store.join(format!("day{}.txt", day));
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                let text = input::normalise(&text);
+                let lines = match text.lines().count() {
+                    1 => String::from("1 line"),
+                    count => format!("{} lines", count),
+                };
+                println!("  Day {:>2}  {:016x}  {:>11}", day, input::checksum(&text), lines);
+            }
+            Err(_) if days::find(day).is_some() => {
+                println!("  Day {:>2}  missing", day);
+                missing.push(day.to_string());
+            }
+            Err(_) => {}
+        }
+    }
+
+    match missing.len() {
+        0 => println!("Every day has an input"),
+        count => println!(
+            "{} of {} days have no input: {}",
+            count,
+            days::DAYS.len(),
+            missing.join(", ")
+        ),
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use common::{Answer, Part};
+
+/// Identifies one answer: a part of a day, solved for one particular input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub day: u8,
+    pub part: Part,
+    /// The checksum of the input, so an answer stays with its input wherever the file lives.
+    pub input: u64,
+}
+
+/// The answers that are known to be right, stored as one line per answer:
+///
+/// ```text
+/// <day> <part> <input checksum> <answer>
+/// ```
+///
+/// Newlines and backslashes in answers are escaped so every answer fits on its line.
+#[derive(Debug, Default)]
+pub struct Ledger {
+    answers: BTreeMap<Key, String>,
+}
+
+const HEADER: &str = "\
+# Known good answers, checked by `aoc verify` and added to by `aoc verify --record`.
+# <day> <part> <input checksum> <answer>
+";
+
+impl Ledger {
+    /// Loads the ledger at `path`, which starts out empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Ledger, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Ledger::default()),
+            Err(err) => return Err(format!("could not read '{}': {}", path.display(), err)),
+        };
+
+        let mut ledger = Ledger::default();
+        for (i, line) in text.lines().enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, answer) = parse_entry(line).ok_or_else(|| {
+                format!(
+                    "{}:{}: expected `<day> <part> <input checksum> <answer>`",
+                    path.display(),
+                    i + 1
+                )
+            })?;
+            ledger.answers.insert(key, answer);
+        }
+
+        Ok(ledger)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut text = String::from(HEADER);
+        for (key, answer) in &self.answers {
+            let _ = writeln!(
+                text,
+                "{} {} {:016x} {}",
+                key.day,
+                key.part,
+                key.input,
+                escape(answer)
+            );
+        }
+
+        fs::write(path, text)
+            .map_err(|err| format!("could not write '{}': {}", path.display(), err))
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&str> {
+        self.answers.get(key).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, key: Key, answer: &Answer) {
+        self.answers.insert(key, answer.to_string());
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(Key, String)> {
+    let mut fields = line.splitn(4, ' ');
+
+    let day = fields.next()?.parse::<u8>().ok()?;
+    let part = Part::from_number(fields.next()?.parse::<u8>().ok()?)?;
+    let input = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let answer = unescape(fields.next()?)?;
+
+    Some((Key { day, part, input }, answer))
+}
+
+fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> Option<String> {
+    let mut unescaped = String::new();
+    let mut chars = answer.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                'n' => unescaped.push('\n'),
+                '\\' => unescaped.push('\\'),
+                _ => return None,
+            },
+            c => unescaped.push(c),
+        }
+    }
+
+    Some(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    // A ledger file of its own for each test, so they can run at the same time
+    fn scratch_file(name: &str) -> std::path::PathBuf {
+        env::temp_dir().join(format!("aoc-ledger-{}-{}.txt", process::id(), name))
+    }
+
+    #[test]
+    fn screens_with_newlines_and_backslashes_survive_saving() {
+        let screen = Answer::Screen(String::from("#..#\\\n\\n..#.\n"));
+        let key = Key { day: 10, part: Part::Two, input: 0xfeed };
+
+        let mut ledger = Ledger::default();
+        ledger.insert(key, &screen);
+        ledger.insert(Key { part: Part::One, ..key }, &Answer::Signed(-13140));
+
+        let path = scratch_file("round-trip");
+        ledger.save(&path).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let loaded = Ledger::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(text.contains("10 2 000000000000feed #..#\\\\\\n\\\\n..#.\\n\n"));
+        assert_eq!(loaded.get(&key), Some("#..#\\\n\\n..#.\n"));
+        assert_eq!(loaded.get(&Key { part: Part::One, ..key }), Some("-13140"));
+    }
+
+    #[test]
+    fn escaping_is_undone_exactly() {
+        for answer in ["", "\\", "\\n", "\n\n", "a\\\\b\nc", "\\\n"] {
+            assert_eq!(unescape(&escape(answer)).as_deref(), Some(answer));
+        }
+    }
+
+    #[test]
+    fn malformed_entries_are_rejected() {
+        let entry = parse_entry("3 1 00000000000000ff 157").unwrap();
+        assert_eq!(entry, (Key { day: 3, part: Part::One, input: 0xff }, String::from("157")));
+
+        for line in [
+            "3 1 ff",
+            "3 3 ff 157",
+            "three 1 ff 157",
+            "3 1 nothex 157",
+            "300 1 ff 157",
+            "3 1 ff trailing\\",
+            "3 1 ff unknown\\t",
+        ] {
+            assert_eq!(parse_entry(line), None, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn loading_points_at_the_malformed_line() {
+        let path = scratch_file("malformed");
+        fs::write(&path, "# comment\n\n1 1 ff 24000\n1 x ff 45000\n").unwrap();
+        let err = Ledger::load(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert!(err.ends_with(":4: expected `<day> <part> <input checksum> <answer>`"), "{}", err);
+    }
+}
@@ -0,0 +1,482 @@
+mod bench;
+mod cpu;
+mod days;
+mod inputs;
+mod ledger;
+mod parallel;
+mod scaffold;
+mod stress;
+mod verify;
+mod visualise;
+
+use std::env;
+use std::num::NonZero;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::slice;
+use std::str::FromStr;
+use std::thread;
+
+use common::explain;
+use common::input::Source;
+use common::output::{self, Format};
+use common::visual::{self, Options};
+use common::Part;
+
+use days::Day;
+
+const USAGE: &str = "\
+Usage:
+    aoc run --day <N> [--part <1|2>] [--input <PATH>] [--format <text|json>] [--explain]
+    aoc run (--all | --days <N-M>) [--input <DIR>] [--jobs <N>] [--format <text|json>]
+    aoc verify [--day <N> | --days <N-M>] [--input <PATH>] [--answers <FILE>] [--record]
+    aoc bench [--day <N> | --days <N-M>] [--input <PATH>] [--iterations <N>]
+              [--format <text|json>]
+    aoc generate --day <N> [--seed <N>] [--size <N>]
+    aoc stress [--day <N>] [--seed <N>] [--size <N>] [--count <N>]
+    aoc visualise --day <N> [--part <1|2>] [--input <PATH>] [--speed <N>] [--step]
+                  [--no-colour] [--dump <DIR>]
+    aoc new --day <N>
+    aoc inputs import [--day <N>] --input <PATH> [--profile <NAME>] [--force]
+    aoc inputs list [--profile <NAME>]
+
+<PATH> is either a single input file, a directory of inputs, or '-' to read
+from stdin. A directory may hold 'dayN.txt' files or mirror this repository's
+'dayN/src/input.txt' layout, which is also where inputs are looked up by default.
+Instead of '--input', '--profile <NAME>' reads the inputs imported for <NAME>.
+
+Running every day, or those numbered from N to M with '--days', solves them on
+<N> threads at once (as many as there are CPUs by default) and prints a table of
+the answers with how long parsing and each part took, and how much CPU time each
+day used altogether. A day that panics is reported as failed, and the others still
+run.
+
+With '--format json', 'run' prints one JSON object per line for each part, holding
+the answer and its type (or the error), a checksum of the input and how long parsing
+and solving took.
+
+With '--explain', 'run' also prints the notes a day leaves on its way to the answers,
+e.g. each elf's total for day 1 or how many items each monkey inspected for day 11.
+They're printed after the answers, or as one JSON object per note with '--format json'.
+
+'verify' checks every day, or just the ones given, against the known good answers
+in <FILE> (by default 'answers.txt' at the root of this repository). Days without
+an input are skipped. With '--record', answers that aren't known yet are added to
+<FILE>; an answer that fails has to be removed from <FILE> before it's recorded again.
+
+'bench' times parsing and each part of every day, or just the ones given, over
+<N> iterations (10 by default) and reports the min, median and max of each.
+Days without an input are skipped.
+
+'generate' prints a random input for the day, in the puzzle's format. The same
+seed (0 by default) always gives the same input. The size (100 by default) is
+roughly how many records the input has: elves, moves, trees along a side, etc.
+
+'stress' generates <N> inputs (10 by default) for every day, or just the one
+given, from consecutive seeds, and checks the day's answers against a slow but
+simple brute force solver. It reports how long both took on average.
+
+'visualise' plays a day's simulation of the given part (1 by default) in the
+terminal, for days 9, 10, 12 and 14. It shows <N> frames a second (10 by default,
+from 0.1 to 10000); while it plays, press Enter to pause and then to step a frame
+at a time, 'c' and Enter to carry on, '+' or '-' and Enter to double or halve the
+speed, and 'q' and Enter to stop. '--step' starts out paused. Colours are left out with '--no-colour', when
+NO_COLOR is set or when the output isn't a terminal. With '--dump', each frame is
+written to a numbered text file in <DIR> instead.
+
+'new' adds a crate for day <N> to this repository, with stub solvers, a test module
+reading an empty 'example.txt' and the day added to the workspace and this runner.
+
+'inputs import' copies inputs into 'inputs/<NAME>/dayN.txt' in this repository,
+so that several people's inputs can be kept side by side and picked with
+'--profile <NAME>' ('default' unless given). <PATH> is read the same way as for
+'run', and without '--day' every input found in a directory is imported. An
+input that's already there is only replaced with '--force'. 'inputs list' shows
+the checksum of each imported input and which days are still missing one.";
+
+enum Selection {
+    All { input: Source, days: RangeInclusive<u8> },
+    Day { number: u8, part: Option<Part>, input: Source },
+}
+
+enum Command {
+    Run { selection: Selection, jobs: usize, format: Format, explain: bool },
+    Verify { selection: Selection, answers: PathBuf, record: bool },
+    Bench { selection: Selection, iterations: usize, format: Format },
+    Generate { number: u8, seed: u64, size: usize },
+    Stress { number: Option<u8>, seed: u64, size: usize, count: u64 },
+    Visualise { number: u8, part: Part, input: Source, options: Options },
+    New { number: u8 },
+    ImportInputs { number: Option<u8>, from: Source, store: PathBuf, force: bool },
+    ListInputs { profile: String, store: PathBuf },
+}
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // Failures are reported as they happen, so all that's left here is the exit code
+    let succeeded = match command {
+        Command::Run { selection: Selection::All { input, days }, jobs, format, .. } => {
+            select_days(&days).is_some_and(|days| parallel::run(days, &input, jobs, format))
+        }
+        Command::Run { selection: Selection::Day { number, part, input }, format, explain, .. } => {
+            if explain {
+                explain::enable();
+            }
+            find_day(number).is_some_and(|day| run_day(day, part, &input, format))
+        }
+        Command::Verify { selection: Selection::All { input, days }, answers, record } => {
+            select_days(&days).is_some_and(|days| verify::verify(days, &input, &answers, record))
+        }
+        Command::Verify { selection: Selection::Day { number, input, .. }, answers, record } => {
+            find_day(number)
+                .is_some_and(|day| verify::verify(slice::from_ref(day), &input, &answers, record))
+        }
+        Command::Bench { selection: Selection::All { input, days }, iterations, format } => {
+            select_days(&days).is_some_and(|days| bench::bench(days, &input, iterations, format))
+        }
+        Command::Bench { selection: Selection::Day { number, input, .. }, iterations, format } => {
+            find_day(number)
+                .is_some_and(|day| bench::bench(slice::from_ref(day), &input, iterations, format))
+        }
+        Command::Generate { number, seed, size } => find_day(number)
+            .map(|day| stress::generate(day, seed, size))
+            .is_some(),
+        Command::Stress { number: None, seed, size, count } => {
+            stress::stress(days::DAYS, seed, size, count)
+        }
+        Command::Stress { number: Some(number), seed, size, count } => find_day(number)
+            .is_some_and(|day| stress::stress(slice::from_ref(day), seed, size, count)),
+        Command::Visualise { number, part, input, options } => find_day(number)
+            .is_some_and(|day| visualise::visualise(day, part, &input, &options)),
+        Command::New { number } => scaffold::new_day(&workspace_dir(), number),
+        Command::ImportInputs { number, from, store, force } => {
+            inputs::import(number, &from, &store, force)
+        }
+        Command::ListInputs { profile, store } => {
+            inputs::list(&profile, &store);
+            true
+        }
+    };
+
+    match succeeded {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+fn find_day(number: u8) -> Option<&'static Day> {
+    let day = days::find(number);
+    if day.is_none() {
+        eprintln!("error: day {} has no solver", number);
+    }
+    day
+}
+
+fn select_days(range: &RangeInclusive<u8>) -> Option<&'static [Day]> {
+    let selected = days::select(range);
+    if selected.is_empty() {
+        eprintln!("error: no days from {} to {} have a solver", range.start(), range.end());
+        return None;
+    }
+    Some(selected)
+}
+
+#[derive(PartialEq)]
+enum Mode {
+    Run,
+    Verify,
+    Bench,
+    Generate,
+    Stress,
+    Visualise,
+    New,
+    ImportInputs,
+    ListInputs,
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut args = args.iter();
+
+    let mode = match args.next().map(String::as_str) {
+        Some("run") => Mode::Run,
+        Some("verify") => Mode::Verify,
+        Some("bench") => Mode::Bench,
+        Some("generate") => Mode::Generate,
+        Some("stress") => Mode::Stress,
+        Some("visualise") => Mode::Visualise,
+        Some("new") => Mode::New,
+        Some("inputs") => match args.next().map(String::as_str) {
+            Some("import") => Mode::ImportInputs,
+            Some("list") => Mode::ListInputs,
+            _ => return Err(String::from("'inputs' expects 'import' or 'list'")),
+        },
+        Some(command) => return Err(format!("unknown command '{}'", command)),
+        None => return Err(String::from("no command given")),
+    };
+
+    let mut all = false;
+    let mut range = None;
+    let mut number = None;
+    let mut part = None;
+    let mut input = None;
+    let mut answers = None;
+    let mut record = false;
+    let mut explain = false;
+    let mut profile = None;
+    let mut force = false;
+    let mut iterations = 10;
+    let mut format = Format::Text;
+    let mut jobs = thread::available_parallelism().map_or(1, NonZero::get);
+    let mut seed = 0;
+    let mut size = 100;
+    let mut count = 10;
+    let mut options = Options::default();
+    let generating = mode == Mode::Generate || mode == Mode::Stress;
+    let reads_input = !generating && mode != Mode::New && mode != Mode::ListInputs;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" if mode == Mode::Run => all = true,
+            "--days" if mode == Mode::Run || mode == Mode::Verify || mode == Mode::Bench => {
+                range = Some(parse_range(args.next())?)
+            }
+            "--jobs" if mode == Mode::Run => {
+                jobs = parse_flag_value(arg, args.next())?;
+                if jobs == 0 {
+                    return Err(String::from("'--jobs' must be at least 1"));
+                }
+            }
+            "--day" => number = Some(parse_flag_value(arg, args.next())?),
+            "--explain" if mode == Mode::Run => explain = true,
+            "--part" if mode == Mode::Run || mode == Mode::Visualise => {
+                let number = parse_flag_value(arg, args.next())?;
+                match Part::from_number(number) {
+                    Some(selected) => part = Some(selected),
+                    None => return Err(format!("part must be 1 or 2, got {}", number)),
+                }
+            }
+            "--input" if reads_input => match args.next().map(String::as_str) {
+                Some("-") => input = Some(Source::Stdin),
+                Some(path) => input = Some(Source::Path(PathBuf::from(path))),
+                None => return Err(String::from("'--input' expects a path")),
+            },
+            "--profile" if !generating && mode != Mode::New => match args.next() {
+                Some(name) if inputs::is_valid_profile(name) => profile = Some(name.clone()),
+                _ => {
+                    let message = "'--profile' expects a name of letters, digits, '-' and '_'";
+                    return Err(String::from(message));
+                }
+            },
+            "--force" if mode == Mode::ImportInputs => force = true,
+            "--answers" if mode == Mode::Verify => match args.next() {
+                Some(path) => answers = Some(PathBuf::from(path)),
+                None => return Err(String::from("'--answers' expects a path")),
+            },
+            "--record" if mode == Mode::Verify => record = true,
+            "--iterations" if mode == Mode::Bench => {
+                iterations = parse_flag_value(arg, args.next())?;
+                if iterations == 0 {
+                    return Err(String::from("'--iterations' must be at least 1"));
+                }
+            }
+            "--seed" if generating => seed = parse_flag_value(arg, args.next())?,
+            "--size" if generating => size = parse_flag_value(arg, args.next())?,
+            "--count" if mode == Mode::Stress => {
+                count = parse_flag_value(arg, args.next())?;
+                if count == 0 {
+                    return Err(String::from("'--count' must be at least 1"));
+                }
+            }
+            "--speed" if mode == Mode::Visualise => {
+                options.speed = parse_flag_value(arg, args.next())?;
+                if !(visual::MIN_SPEED..=visual::MAX_SPEED).contains(&options.speed) {
+                    return Err(format!(
+                        "'--speed' must be from {} to {}",
+                        visual::MIN_SPEED,
+                        visual::MAX_SPEED
+                    ));
+                }
+            }
+            "--step" if mode == Mode::Visualise => options.step = true,
+            "--no-colour" if mode == Mode::Visualise => options.colour = false,
+            "--dump" if mode == Mode::Visualise => match args.next() {
+                Some(path) => options.dump = Some(PathBuf::from(path)),
+                None => return Err(String::from("'--dump' expects a directory")),
+            },
+            "--format" if mode == Mode::Run || mode == Mode::Bench => {
+                let name = args.next().map(String::as_str).unwrap_or("");
+                format = Format::from_name(name)
+                    .ok_or_else(|| String::from("'--format' expects 'text' or 'json'"))?;
+            }
+            arg => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    // Generated inputs and new days don't need an input
+    match (&mode, number) {
+        (Mode::Generate, Some(number)) => return Ok(Command::Generate { number, seed, size }),
+        (Mode::Generate, None) => return Err(String::from("'generate' needs '--day <N>'")),
+        (Mode::Stress, number) => return Ok(Command::Stress { number, seed, size, count }),
+        (Mode::New, Some(number @ 1..=25)) => return Ok(Command::New { number }),
+        (Mode::New, Some(number)) => {
+            return Err(format!("there are only puzzles for days 1 to 25, not {}", number))
+        }
+        (Mode::New, None) => return Err(String::from("'new' needs '--day <N>'")),
+        _ => {}
+    }
+
+    // The profile is where inputs are imported to, and otherwise where they're read from
+    let store = inputs::profile_dir(
+        &workspace_dir(),
+        profile.as_deref().unwrap_or(inputs::DEFAULT_PROFILE),
+    );
+    match mode {
+        Mode::ImportInputs => {
+            let from = input.ok_or_else(|| String::from("'inputs import' needs '--input <PATH>'"))?;
+            let is_dir = matches!(&from, Source::Path(path) if path.is_dir());
+            if number.is_none() && !is_dir {
+                return Err(String::from("importing a single input needs '--day <N>'"));
+            }
+            return Ok(Command::ImportInputs { number, from, store, force });
+        }
+        Mode::ListInputs => {
+            let profile = profile.unwrap_or_else(|| String::from(inputs::DEFAULT_PROFILE));
+            return Ok(Command::ListInputs { profile, store });
+        }
+        _ => {}
+    }
+
+    let input = match (input, profile) {
+        (Some(_), Some(_)) => {
+            return Err(String::from("'--input' cannot be combined with '--profile'"))
+        }
+        (Some(input), None) => input,
+        (None, Some(profile)) if !store.is_dir() => {
+            return Err(format!("no inputs have been imported for profile '{}'", profile))
+        }
+        (None, Some(_)) => Source::Path(store),
+        (None, None) => Source::Path(workspace_dir()),
+    };
+
+    // Only one day is shown at a time
+    if mode == Mode::Visualise {
+        let number = number.ok_or_else(|| String::from("'visualise' needs '--day <N>'"))?;
+        let part = part.unwrap_or(Part::One);
+        return Ok(Command::Visualise { number, part, input, options });
+    }
+
+    // Verifying and benchmarking cover every day unless told otherwise
+    if all && range.is_some() {
+        return Err(String::from("'--all' cannot be combined with '--days'"));
+    }
+    let all = all || range.is_some() || (mode != Mode::Run && number.is_none());
+
+    let selection = match (all, number) {
+        (true, None) if part.is_none() => match input {
+            Source::Path(ref path) if path.is_dir() => {
+                Selection::All { input, days: range.unwrap_or(1..=25) }
+            }
+            _ => return Err(String::from("covering every day needs a directory of inputs")),
+        },
+        (true, _) => {
+            let message = "'--all' and '--days' cannot be combined with '--day' or '--part'";
+            return Err(String::from(message));
+        }
+        (false, Some(number)) => Selection::Day { number, part, input },
+        (false, None) => return Err(String::from("either '--day <N>' or '--all' is required")),
+    };
+
+    match mode {
+        Mode::Run => match selection {
+            Selection::All { .. } if explain => {
+                Err(String::from("'--explain' only works with '--day <N>'"))
+            }
+            selection => Ok(Command::Run { selection, jobs, format, explain }),
+        },
+        Mode::Verify => Ok(Command::Verify {
+            selection,
+            answers: answers.unwrap_or_else(|| workspace_dir().join("answers.txt")),
+            record,
+        }),
+        Mode::Bench => Ok(Command::Bench { selection, iterations, format }),
+        Mode::Generate | Mode::Stress | Mode::New | Mode::ImportInputs | Mode::ListInputs => {
+            unreachable!("handled before reading the input")
+        }
+        Mode::Visualise => unreachable!("handled before selecting days"),
+    }
+}
+
+// A range of days written as `N-M`, or a single day
+fn parse_range(value: Option<&String>) -> Result<RangeInclusive<u8>, String> {
+    let value = value.map(String::as_str).unwrap_or("");
+    let (first, last) = value.split_once('-').unwrap_or((value, value));
+    match (first.parse::<u8>(), last.parse::<u8>()) {
+        (Ok(first), Ok(last)) if first <= last => Ok(first..=last),
+        _ => Err(String::from("'--days' expects a range of days like '3-7'")),
+    }
+}
+
+fn parse_flag_value<T: FromStr>(flag: &str, value: Option<&String>) -> Result<T, String> {
+    match value.map(|value| value.parse::<T>()) {
+        Some(Ok(value)) => Ok(value),
+        _ => Err(format!("'{}' expects a number", flag)),
+    }
+}
+
+/// The root of this repository. Without '--input', inputs are looked up in here next to
+/// each day's sources as `dayN/src/input.txt`.
+fn workspace_dir() -> PathBuf {
+    let runner_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    runner_dir.parent().unwrap_or(runner_dir).to_path_buf()
+}
+
+/// Prints a day's answers, rendering any errors along the way.
+/// Returns whether every part was solved.
+fn run_day(day: &Day, part: Option<Part>, input: &Source, format: Format) -> bool {
+    let input = match input.load(day.number) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return false;
+        }
+    };
+
+    if format == Format::Text {
+        println!("Day {}", day.number);
+    }
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    let solved = (day.solve)(&input.text, &parts);
+
+    let mut solved_all = true;
+    for record in output::records(day.number, &input, &solved) {
+        solved_all &= record.outcome.is_ok();
+        match format {
+            Format::Text => record.print_text("  "),
+            Format::Json => println!("{}", record.to_json()),
+        }
+    }
+    if let Ok(solved) = &solved {
+        match format {
+            Format::Text => output::print_notes_text(&solved.notes, "  "),
+            Format::Json => solved.notes.iter().for_each(|note| {
+                println!("{}", output::note_json(day.number, note));
+            }),
+        }
+    }
+
+    solved_all
+}
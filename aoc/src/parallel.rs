@@ -0,0 +1,261 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common::input::Source;
+use common::output::{self, Format};
+use common::{diagnostic, Answer, Part};
+
+use crate::cpu;
+use crate::days::Day;
+
+/// How one part of a day went.
+enum Outcome {
+    Solved(Answer),
+    Failed,
+    /// The day panicked, so nothing it worked out survived
+    Panicked,
+    /// The input couldn't be loaded or parsed, so the part was never tried
+    NotReached,
+}
+
+/// Everything about running one day, already rendered where it has to be, so that it can be
+/// handed back from the thread that ran it.
+struct Report {
+    day: u8,
+    parts: [(Outcome, Option<Duration>); 2],
+    parse_time: Option<Duration>,
+    /// The CPU time the day used, loading the input included, or `None` when the platform
+    /// can't measure it. Time spent waiting for a CPU or for the disk isn't counted.
+    cpu_time: Option<Duration>,
+    errors: Vec<String>,
+    json: Vec<String>,
+}
+
+impl Report {
+    fn new(day: &Day) -> Report {
+        Report {
+            day: day.number,
+            parts: [(Outcome::NotReached, None), (Outcome::NotReached, None)],
+            parse_time: None,
+            cpu_time: None,
+            errors: Vec::new(),
+            json: Vec::new(),
+        }
+    }
+
+    fn succeeded(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Solves every one of `days` on up to `jobs` threads at once, then prints a table of the
+/// answers sorted by day along with how long each took. Returns whether every day was solved.
+pub fn run(days: &[Day], input: &Source, jobs: usize, format: Format) -> bool {
+    let start = Instant::now();
+    let threads = jobs.clamp(1, days.len().max(1));
+
+    // NOTE: A panicking day is reported in its row of the table, so the default hook
+    // printing it as well would only garble the output. Nothing else runs meanwhile.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    // Each thread takes the next day nobody has started on until there are none left
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    // NOTE: A day that panics is only that day's failure. Nothing it could
+                    // have left half done is shared with the others.
+                    let start = cpu::thread_time();
+                    let mut report = panic::catch_unwind(AssertUnwindSafe(|| run_day(day, input)))
+                        .unwrap_or_else(|panic| panicked(day, panic.as_ref()));
+                    report.cpu_time = cpu::thread_time()
+                        .zip(start)
+                        .and_then(|(end, start)| end.checked_sub(start));
+                    reports.lock().unwrap_or_else(|err| err.into_inner()).push(report);
+                }
+            });
+        }
+    });
+    let wall_time = start.elapsed();
+    panic::set_hook(hook);
+
+    let mut reports = reports.into_inner().unwrap_or_else(|err| err.into_inner());
+    reports.sort_by_key(|report| report.day);
+
+    match format {
+        Format::Text => print_text(&reports, wall_time, threads),
+        Format::Json => reports.iter().flat_map(|report| &report.json).for_each(|json| {
+            println!("{}", json);
+        }),
+    }
+
+    // Keep going when a day fails so one missing input doesn't hide every other answer
+    let failures = reports.iter().filter(|report| !report.succeeded()).count();
+    if failures > 0 {
+        eprintln!("error: {} of {} days failed", failures, reports.len());
+    }
+    failures == 0
+}
+
+fn run_day(day: &Day, source: &Source) -> Report {
+    let mut report = Report::new(day);
+
+    match source.load(day.number) {
+        Ok(input) => {
+            let solved = (day.solve)(&input.text, &Part::BOTH);
+            for record in output::records(day.number, &input, &solved) {
+                report.json.push(record.to_json());
+                if let Err(err) = record.outcome {
+                    report.errors.push(diagnostic::render(err, &input.name, &input.text));
+                }
+            }
+
+            if let Ok(solved) = &solved {
+                report.parse_time = Some(solved.parse_time);
+                for (slot, part) in report.parts.iter_mut().zip(&solved.parts) {
+                    let outcome = match &part.answer {
+                        Ok(answer) => Outcome::Solved(answer.clone()),
+                        Err(_) => Outcome::Failed,
+                    };
+                    *slot = (outcome, Some(part.time));
+                }
+            }
+        }
+        Err(err) => report.errors.push(format!("error: {}\n", err)),
+    }
+
+    report
+}
+
+// The report for a day that panicked, where the panic's message is the only error
+fn panicked(day: &Day, panic: &(dyn Any + Send)) -> Report {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("no message");
+
+    let mut report = Report::new(day);
+    report.parts = [(Outcome::Panicked, None), (Outcome::Panicked, None)];
+    report.errors.push(format!("error: day {} panicked: {}\n", day.number, message));
+    // Whatever the day got as far as is lost, so all there is to say is that it failed
+    report.json.push(format!(
+        "{{\"day\":{},\"part\":null,\"error\":\"panicked: {}\"}}",
+        day.number,
+        output::escape_json(message)
+    ));
+    report
+}
+
+fn print_text(reports: &[Report], wall_time: Duration, threads: usize) {
+    const HEADINGS: [&str; 7] = ["Day", "Part 1", "Part 2", "Parse", "Part 1", "Part 2", "CPU"];
+    // The answers are lined up on the left, and the day and the times on the right
+    const LEFT_ALIGNED: [bool; 7] = [false, true, true, false, false, false, false];
+
+    let time = |time: Option<Duration>| match time {
+        Some(time) => format!("{:.1?}", time),
+        None => String::from("-"),
+    };
+    let rows = reports
+        .iter()
+        .map(|report| {
+            let [(one, one_time), (two, two_time)] = &report.parts;
+            [
+                report.day.to_string(),
+                cell(one),
+                cell(two),
+                time(report.parse_time),
+                time(*one_time),
+                time(*two_time),
+                time(report.cpu_time),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = HEADINGS.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |row: [&str; 7]| {
+        let cells = row.iter().zip(widths).zip(LEFT_ALIGNED).map(|((cell, width), left)| {
+            match left {
+                true => format!("{:<width$}", cell),
+                false => format!("{:>width$}", cell),
+            }
+        });
+        println!("{}", cells.collect::<Vec<_>>().join("  ").trim_end());
+    };
+    print_row(HEADINGS);
+    for row in &rows {
+        print_row(row.each_ref().map(String::as_str));
+    }
+
+    let days = plural(reports.len(), "day");
+    let threads = plural(threads, "thread");
+    match reports.iter().map(|report| report.cpu_time).sum::<Option<Duration>>() {
+        Some(cpu_time) => println!(
+            "\n{} in {:.1?} on {}, using {:.1?} of CPU time between them",
+            days, wall_time, threads, cpu_time
+        ),
+        None => println!("\n{} in {:.1?} on {}", days, wall_time, threads),
+    }
+
+    // Screens don't fit in a table, so they're drawn out underneath it
+    for report in reports {
+        for (part, (outcome, _)) in Part::BOTH.iter().zip(&report.parts) {
+            if let Outcome::Solved(Answer::Screen(screen)) = outcome {
+                println!("\nDay {} part {}:\n{}", report.day, part, screen.trim_end());
+            }
+        }
+    }
+
+    for report in reports {
+        for error in &report.errors {
+            eprint!("\n{}", error);
+        }
+    }
+}
+
+fn cell(outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Solved(Answer::Screen(_)) => String::from("(screen below)"),
+        Outcome::Solved(answer) => answer.to_string(),
+        Outcome::Failed => String::from("error"),
+        Outcome::Panicked => String::from("panicked"),
+        Outcome::NotReached => String::from("-"),
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {}", noun),
+        count => format!("{} {}s", count, noun),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::DAYS;
+
+    #[test]
+    fn a_panicking_day_fails_in_its_own_row() {
+        let panic = panic::catch_unwind(|| panic!("out of {}", "crates")).unwrap_err();
+        let report = panicked(&DAYS[4], panic.as_ref());
+
+        assert!(!report.succeeded());
+        assert_eq!(report.errors, ["error: day 5 panicked: out of crates\n"]);
+        assert_eq!(report.parts.each_ref().map(|(outcome, _)| cell(outcome)), ["panicked"; 2]);
+        assert_eq!(report.json, [r#"{"day":5,"part":null,"error":"panicked: out of crates"}"#]);
+    }
+}
@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::Path;
+
+use crate::days;
+
+// The files of a new day's crate, with `__DAY__` standing in for its number
+const TEMPLATES: &[(&str, &str)] = &[
+    ("Cargo.toml", include_str!("../templates/Cargo.toml.template")),
+    ("src/main.rs", include_str!("../templates/main.rs.template")),
+    ("src/lib.rs", include_str!("../templates/lib.rs.template")),
+    ("src/error.rs", include_str!("../templates/error.rs.template")),
+    ("src/stress.rs", include_str!("../templates/stress.rs.template")),
+    ("src/example.txt", ""),
+];
+
+/// A file that already lists every day, one per line, and the line it needs for a new one.
+struct Listing {
+    path: &'static str,
+    // How each day's line starts once it's trimmed, up to the day's number
+    prefix: &'static str,
+    line: fn(u8) -> String,
+}
+
+const LISTINGS: &[Listing] = &[
+    Listing {
+        path: "Cargo.toml",
+        prefix: "\"day",
+        line: |day| format!("    \"day{}\",", day),
+    },
+    Listing {
+        path: "aoc/Cargo.toml",
+        prefix: "day",
+        line: |day| format!("day{0} = {{ path = \"../day{0}\", features = [\"stress\"] }}", day),
+    },
+    Listing {
+        path: "aoc/src/days.rs",
+        prefix: "day!(day",
+        line: |day| format!("    day!(day{0}::Day{0}),", day),
+    },
+];
+
+/// Creates the crate for a new day under `workspace` and adds it to the workspace and to
+/// this runner. Returns whether it was created.
+pub fn new_day(workspace: &Path, day: u8) -> bool {
+    let directory = workspace.join(format!("day{}", day));
+    if days::find(day).is_some() || directory.exists() {
+        eprintln!("error: day {} already exists in '{}'", day, directory.display());
+        return false;
+    }
+
+    // Every listing is checked before anything is written, so a listing that can't be
+    // updated doesn't leave a crate behind that the workspace doesn't know about
+    let mut listings = Vec::new();
+    for listing in LISTINGS {
+        let path = workspace.join(listing.path);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("error: could not read '{}': {}", path.display(), err);
+                return false;
+            }
+        };
+        match insert_day(&text, listing.prefix, day, &(listing.line)(day)) {
+            Ok(text) => listings.push((path, text)),
+            Err(problem) => {
+                eprintln!("error: {} in '{}'", problem, path.display());
+                return false;
+            }
+        }
+    }
+
+    let written = TEMPLATES
+        .iter()
+        .try_for_each(|(name, template)| {
+            let path = directory.join(name);
+            fs::create_dir_all(path.parent().unwrap_or(&directory))?;
+            fs::write(path, template.replace("__DAY__", &day.to_string()))
+        })
+        .and_then(|()| listings.iter().try_for_each(|(path, text)| fs::write(path, text)));
+    if let Err(err) = written {
+        eprintln!("error: could not create day {}: {}", day, err);
+        return false;
+    }
+
+    println!("Created '{}'. Next:", directory.display());
+    println!("  - paste the puzzle's example into 'day{}/src/example.txt'", day);
+    println!("  - save your input as 'day{}/src/input.txt'", day);
+    println!("  - solve 'part_one' and 'part_two' in 'day{}/src/lib.rs'", day);
+    true
+}
+
+// Adds `line` to the lines starting with `prefix` and a day's number, keeping them in order,
+// or says why it can't
+fn insert_day(text: &str, prefix: &str, day: u8, line: &str) -> Result<String, String> {
+    let listed = |text: &str| -> Option<u8> {
+        let rest = text.trim_start().strip_prefix(prefix)?;
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        rest[..digits].parse().ok()
+    };
+
+    let lines = text.lines().collect::<Vec<&str>>();
+    let first = lines
+        .iter()
+        .position(|line| listed(line).is_some())
+        .ok_or("could not find where the days are listed")?;
+    if lines.iter().any(|line| listed(line) == Some(day)) {
+        return Err(format!("day {} is already listed", day));
+    }
+    let at = lines
+        .iter()
+        .rposition(|line| listed(line).is_some_and(|listed| listed < day))
+        .map_or(first, |i| i + 1);
+
+    let mut lines = lines.into_iter().map(String::from).collect::<Vec<_>>();
+    lines.insert(at, line.to_string());
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    const DAYS: &str = include_str!("days.rs");
+
+    fn insert(listing: usize, text: &str, day: u8) -> Result<String, String> {
+        let listing = &LISTINGS[listing];
+        insert_day(text, listing.prefix, day, &(listing.line)(day))
+    }
+
+    #[test]
+    fn adds_the_next_day_to_the_days_table() {
+        let text = insert(2, DAYS, 15).unwrap();
+        assert!(text.contains("    day!(day14::Day14, visualise),\n    day!(day15::Day15),\n];"));
+        assert_eq!(text.lines().count(), DAYS.lines().count() + 1);
+    }
+
+    #[test]
+    fn adds_a_day_out_of_order_between_the_days_around_it() {
+        let workspace = "members = [\n    \"day1\",\n    \"day3\",\n    \"day10\",\n]";
+        let text = insert(0, workspace, 2).unwrap();
+        assert!(text.contains("    \"day1\",\n    \"day2\",\n    \"day3\","));
+
+        // Day 4 goes after day 3, not after day 10 just because "10" sorts before "4"
+        let text = insert(0, workspace, 4).unwrap();
+        assert!(text.contains("    \"day3\",\n    \"day4\",\n    \"day10\","));
+
+        // A day before every other goes first, after whatever comes before the days
+        let dependencies = "[dependencies]\ncommon = { path = \"../common\" }\nday5 = {}\n";
+        let text = insert(1, dependencies, 1).unwrap();
+        let day1 = "day1 = { path = \"../day1\", features = [\"stress\"] }";
+        assert!(text.ends_with(&format!("common\" }}\n{}\nday5 = {{}}\n", day1)));
+    }
+
+    #[test]
+    fn refuses_a_day_that_already_exists() {
+        assert_eq!(insert(2, DAYS, 9), Err(String::from("day 9 is already listed")));
+        assert!(insert(2, "pub const DAYS: &[Day] = &[];\n", 1).is_err());
+
+        // Neither a day the runner knows nor a directory already there gets overwritten
+        let workspace = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        fs::create_dir_all(workspace.join("day20")).unwrap();
+        let created = (new_day(&workspace, 1), new_day(&workspace, 20));
+        let left_alone = fs::read_dir(workspace.join("day20")).unwrap().count() == 0;
+        fs::remove_dir_all(&workspace).unwrap();
+
+        assert_eq!(created, (false, false));
+        assert!(left_alone);
+    }
+}
@@ -0,0 +1,71 @@
+use std::time::{Duration, Instant};
+
+use common::{stress, Part, Rng};
+
+use crate::days::Day;
+
+/// Prints the input a day's generator writes for `seed` and `size`.
+pub fn generate(day: &Day, seed: u64, size: usize) {
+    print!("{}", (day.generate)(&mut Rng::new(seed), size));
+}
+
+/// Solves `count` generated inputs of each day, starting from `seed`, both with the day's
+/// solver and its brute force one, and reports any input they disagree on along with how
+/// long each solver took on average. Stops early at the last seed there is, rather than
+/// wrapping round to the first.
+pub fn stress(days: &[Day], seed: u64, size: usize, count: u64) -> bool {
+    let mut succeeded = true;
+
+    for day in days {
+        let mut solver_time = Duration::ZERO;
+        let mut brute_force_time = Duration::ZERO;
+        let mut disagreements = 0;
+        let mut solved_inputs = 0u64;
+
+        for seed in (0..count).map_while(|offset| seed.checked_add(offset)) {
+            solved_inputs += 1;
+            let input = (day.generate)(&mut Rng::new(seed), size);
+
+            let solved = (day.solve)(&input, &Part::BOTH);
+            if let Ok(solved) = &solved {
+                solver_time += solved.parse_time;
+                solver_time += solved.parts.iter().map(|part| part.time).sum::<Duration>();
+            }
+
+            let start = Instant::now();
+            let expected = (day.brute_force)(&input);
+            brute_force_time += start.elapsed();
+
+            if let Err(problem) = stress::compare(&solved, &expected) {
+                eprintln!("error: day {} seed {}: {}", day.number, seed, problem);
+                eprintln!(
+                    "  the input is printed by 'aoc generate --day {} --seed {} --size {}'",
+                    day.number, seed, size
+                );
+                disagreements += 1;
+            }
+        }
+
+        // NOTE: There can be more inputs than fit in the `u32` a `Duration` divides by
+        let average = |total: Duration| total.div_f64(solved_inputs.max(1) as f64);
+        match disagreements {
+            0 => println!(
+                "Day {}: {} inputs of size {} agree (solver {:.1?}, brute force {:.1?} on average)",
+                day.number,
+                solved_inputs,
+                size,
+                average(solver_time),
+                average(brute_force_time)
+            ),
+            _ => {
+                println!(
+                    "Day {}: {} of {} inputs disagree",
+                    day.number, disagreements, solved_inputs
+                );
+                succeeded = false;
+            }
+        }
+    }
+
+    succeeded
+}
@@ -0,0 +1,129 @@
+use std::path::Path;
+
+use common::input::{InputError, Source};
+use common::run::Failure;
+use common::{diagnostic, Answer, Part};
+
+use crate::days::Day;
+use crate::ledger::{Key, Ledger};
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+    recorded: usize,
+    skipped: usize,
+}
+
+/// Solves both parts of each day and checks the answers against the ledger at `answers`.
+/// Days without an input are skipped when checking every day, since not every input is
+/// necessarily around. With `record`, answers the ledger doesn't have yet are added to it.
+pub fn verify(days: &[Day], input: &Source, answers: &Path, record: bool) -> bool {
+    let mut ledger = match Ledger::load(answers) {
+        Ok(ledger) => ledger,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return false;
+        }
+    };
+
+    let skip_missing_inputs = days.len() > 1;
+    let mut tally = Tally::default();
+
+    for day in days {
+        let input = match input.load(day.number) {
+            Ok(input) => input,
+            Err(InputError::NotFound { .. }) if skip_missing_inputs => {
+                println!("Day {}: skipped, no input", day.number);
+                tally.skipped += 1;
+                continue;
+            }
+            Err(err) => {
+                eprintln!("error: {}", err);
+                tally.failed += 1;
+                continue;
+            }
+        };
+
+        println!("Day {}", day.number);
+
+        let report = |err: Failure| {
+            eprint!("{}", diagnostic::render(&*err, &input.name, &input.text));
+        };
+        let solved = match (day.solve)(&input.text, &Part::BOTH) {
+            Ok(solved) => solved,
+            Err(err) => {
+                report(err);
+                tally.failed += 1;
+                continue;
+            }
+        };
+
+        let checksum = input.checksum();
+        for result in solved.parts {
+            let part = result.part;
+            let answer = match result.answer {
+                Ok(answer) => answer,
+                Err(err) => {
+                    report(err);
+                    tally.failed += 1;
+                    continue;
+                }
+            };
+
+            let key = Key { day: day.number, part, input: checksum };
+            match ledger.get(&key) {
+                Some(expected) if expected == answer.to_string() => {
+                    println!("  Part {}: pass", part);
+                    tally.passed += 1;
+                }
+                Some(expected) => {
+                    println!("  Part {}: FAIL", part);
+                    print_answer("expected", expected);
+                    print_answer("got", &answer.to_string());
+                    tally.failed += 1;
+                }
+                None if record => {
+                    println!("  Part {}: recorded {}", part, one_line(&answer));
+                    ledger.insert(key, &answer);
+                    tally.recorded += 1;
+                }
+                None => {
+                    println!("  Part {}: missing, got {}", part, one_line(&answer));
+                    tally.missing += 1;
+                }
+            }
+        }
+    }
+
+    if tally.recorded > 0 {
+        if let Err(message) = ledger.save(answers) {
+            eprintln!("error: {}", message);
+            return false;
+        }
+    }
+
+    println!(
+        "{} passed, {} failed, {} missing, {} recorded, {} skipped",
+        tally.passed, tally.failed, tally.missing, tally.recorded, tally.skipped
+    );
+
+    tally.failed == 0
+}
+
+fn print_answer(label: &str, answer: &str) {
+    match answer.contains('\n') {
+        // Screens read better starting on their own line
+        true => println!("    {}:\n{}", label, answer.trim_end()),
+        false => println!("    {}: {}", label, answer),
+    }
+}
+
+// Keeps a screen from spreading over several lines in the middle of a report
+fn one_line(answer: &Answer) -> String {
+    match answer {
+        Answer::Screen(screen) => format!("a screen of {} lines", screen.lines().count()),
+        answer => answer.to_string(),
+    }
+}
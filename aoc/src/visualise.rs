@@ -0,0 +1,36 @@
+use common::diagnostic;
+use common::input::Source;
+use common::visual::Options;
+use common::Part;
+
+use crate::days::Day;
+
+/// Plays a day's simulation of `part` in the terminal, or writes its frames out when
+/// `options` asks for that. Returns whether the day could be shown.
+pub fn visualise(day: &Day, part: Part, input: &Source, options: &Options) -> bool {
+    let Some(visualise) = day.visualise else {
+        eprintln!("error: day {} has no visualisation", day.number);
+        return false;
+    };
+
+    let input = match input.load(day.number) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return false;
+        }
+    };
+
+    match visualise(&input.text, part, options) {
+        Ok(frames) => {
+            if let Some(directory) = &options.dump {
+                println!("Wrote {} frames to {}", frames, directory.display());
+            }
+            true
+        }
+        Err(err) => {
+            eprint!("{}", diagnostic::render(&*err, &input.name, &input.text));
+            false
+        }
+    }
+}
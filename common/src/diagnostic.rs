@@ -0,0 +1,127 @@
+use std::fmt::Write;
+
+/// A stretch of the puzzle input that an error points at. Lines and columns
+/// start at 1 and both columns and lengths are counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub len: usize,
+}
+
+impl Span {
+    /// The span of `token` on the given line. `token` has to be a slice of `line`
+    /// (e.g. from `split_whitespace`), otherwise the span covers the whole line.
+    pub fn of(line_number: usize, line: &str, token: &str) -> Span {
+        match offset_within(line, token) {
+            Some(offset) => Span {
+                line: line_number,
+                column: line[..offset].chars().count() + 1,
+                len: token.chars().count(),
+            },
+            None => Span::line(line_number, line),
+        }
+    }
+
+    pub fn line(line_number: usize, line: &str) -> Span {
+        Span {
+            line: line_number,
+            column: 1,
+            len: line.chars().count(),
+        }
+    }
+
+    /// Points just past the end of a line, for things that are missing from it.
+    pub fn end_of(line_number: usize, line: &str) -> Span {
+        Span {
+            line: line_number,
+            column: line.chars().count() + 1,
+            len: 1,
+        }
+    }
+}
+
+fn offset_within(outer: &str, inner: &str) -> Option<usize> {
+    let start = (inner.as_ptr() as usize).checked_sub(outer.as_ptr() as usize)?;
+    match start + inner.len() <= outer.len() {
+        true => Some(start),
+        false => None,
+    }
+}
+
+/// An error that may be able to point at the part of the input that caused it.
+pub trait Diagnostic: std::error::Error {
+    fn span(&self) -> Option<Span> {
+        None
+    }
+}
+
+// Reading or writing files goes wrong outside of the input, so there's nothing to point at
+impl Diagnostic for std::io::Error {}
+
+/// Renders an error the way rustc does, with the offending input underlined:
+///
+/// ```text
+/// error: unknown opponent weapon choice `D`
+///  --> day2.txt:3:1
+///   |
+/// 3 | D X
+///   | ^
+/// ```
+pub fn render(error: &dyn Diagnostic, name: &str, input: &str) -> String {
+    let mut output = format!("error: {}\n", error);
+
+    let span = match error.span() {
+        Some(span) => span,
+        None => {
+            let _ = writeln!(output, " --> {}", name);
+            return output;
+        }
+    };
+
+    let line = input.lines().nth(span.line.saturating_sub(1)).unwrap_or("");
+    let gutter = " ".repeat(span.line.to_string().len());
+
+    let _ = writeln!(output, "{}--> {}:{}:{}", gutter, name, span.line, span.column);
+    let _ = writeln!(output, "{} |", gutter);
+    let _ = writeln!(output, "{} | {}", span.line, line);
+    let _ = writeln!(
+        output,
+        "{} | {}{}",
+        gutter,
+        " ".repeat(span.column.saturating_sub(1)),
+        "^".repeat(span.len.max(1))
+    );
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::{self, Line};
+
+    #[test]
+    fn spans_point_at_tokens_within_their_line() {
+        let line = "addx  ünf 3";
+        assert_eq!(Span::of(2, line, &line[6..10]), Span { line: 2, column: 7, len: 3 });
+        // Anything that isn't part of the line gets the whole line
+        assert_eq!(Span::of(2, line, "ünf"), Span { line: 2, column: 1, len: 11 });
+        assert_eq!(Span::end_of(2, line), Span { line: 2, column: 12, len: 1 });
+    }
+
+    #[test]
+    fn render_underlines_the_span() {
+        let input = "A Y\nB X\nC D\n";
+        let err = Line { number: 3, text: "C D" }.cursor().literal("X").unwrap_err();
+        let rendered = render(&err, "day2.txt", input);
+        assert_eq!(
+            rendered,
+            "error: expected `X`, found `C`\n --> day2.txt:3:1\n  |\n3 | C D\n  | ^\n"
+        );
+
+        let err = parse::Error { span: Span::end_of(12, "noop"), ..err };
+        let rendered = render(&err, "day10.txt", "noop\n".repeat(12).as_str());
+        assert!(rendered.ends_with("  --> day10.txt:12:5\n   |\n12 | noop\n   |     ^\n"));
+    }
+}
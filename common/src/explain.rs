@@ -0,0 +1,115 @@
+//! Notes that a day can leave about how it got to its answer, e.g. each elf's total in day 1,
+//! to see where a wrong answer went wrong without adding `println!`s.
+//!
+//! Days leave notes with [`explain!`](crate::explain!), which does nothing unless explaining
+//! has been turned on for the current thread with [`enable`]. [`run::solve`](crate::run::solve)
+//! collects the notes left while parsing and while solving each part.
+
+use std::cell::RefCell;
+
+use crate::solution::Part;
+
+/// Something a day noted down on the way to an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Note {
+    /// The step the note was left in: a part, or `None` for parsing
+    pub part: Option<Part>,
+    /// What the note is about, e.g. `elf` or `round`, so that notes can be told apart. It's
+    /// shown in front of the detail.
+    pub topic: &'static str,
+    pub detail: String,
+    /// What the note is made of, by name, e.g. `("elf", "4")` and `("calories", "24000")`,
+    /// for scripts that want the numbers without reading them out of the detail
+    pub fields: Vec<(&'static str, String)>,
+}
+
+thread_local! {
+    // The notes left so far, or `None` when nobody's listening
+    static NOTES: RefCell<Option<Vec<Note>>> = const { RefCell::new(None) };
+}
+
+/// Starts keeping notes on this thread.
+pub fn enable() {
+    NOTES.with_borrow_mut(|notes| {
+        notes.get_or_insert_with(Vec::new);
+    });
+}
+
+/// Stops keeping notes on this thread, and throws away any that haven't been taken.
+pub fn disable() {
+    NOTES.set(None);
+}
+
+pub fn is_enabled() -> bool {
+    NOTES.with_borrow(Option::is_some)
+}
+
+/// Keeps a note, if notes are being kept. Use [`explain!`](crate::explain!) instead, which
+/// only formats the note when it's going to be kept.
+pub fn note(topic: &'static str, detail: String, fields: Vec<(&'static str, String)>) {
+    NOTES.with_borrow_mut(|notes| {
+        if let Some(notes) = notes {
+            notes.push(Note { part: None, topic, detail, fields });
+        }
+    });
+}
+
+/// The notes left since the last time they were taken.
+pub fn take() -> Vec<Note> {
+    NOTES.with_borrow_mut(|notes| notes.as_mut().map(std::mem::take).unwrap_or_default())
+}
+
+/// Leaves a note about `topic` when explaining is turned on, e.g.
+/// `explain!("elf", [elf = 1, calories = 6000], "{elf} carries {calories} calories")`.
+/// Each named value is kept as one of the note's fields and can be used in the detail, which
+/// is shown after the topic, so it reads "elf 1 carries 6000 calories". A note without
+/// fields is formatted like `format!`, e.g. `explain!("elf", "{} is empty", 1)`.
+#[macro_export]
+macro_rules! explain {
+    ($topic:expr, [$($name:ident = $value:expr),+ $(,)?], $detail:literal) => {
+        if $crate::explain::is_enabled() {
+            // All at once, so that a name can't shadow what a later value is made from
+            let ($($name,)+) = ($($value,)+);
+            $crate::explain::note(
+                $topic,
+                format!($detail, $($name = $name),+),
+                vec![$((stringify!($name), $name.to_string())),+],
+            );
+        }
+    };
+    ($topic:expr, $($arg:tt)+) => {
+        if $crate::explain::is_enabled() {
+            $crate::explain::note($topic, format!($($arg)+), Vec::new());
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_are_only_kept_while_explaining() {
+        explain!("elf", "{} is empty", 1);
+        enable();
+        explain!("elf", "{} is empty", 2);
+        let notes = take();
+        disable();
+
+        assert_eq!(notes.len(), 1);
+        assert_eq!((notes[0].topic, notes[0].detail.as_str()), ("elf", "2 is empty"));
+        assert!(notes[0].fields.is_empty());
+    }
+
+    #[test]
+    fn named_values_become_fields() {
+        let elf = (4, 24000);
+        enable();
+        explain!("elf", [elf = elf.0, calories = elf.1], "{elf} carries {calories} calories");
+        let notes = take();
+        disable();
+
+        assert_eq!(notes[0].detail, "4 carries 24000 calories");
+        assert_eq!(notes[0].fields, [("elf", "4".into()), ("calories", "24000".into())]);
+    }
+}
@@ -0,0 +1,280 @@
+//! Throws generated and mangled inputs at a day's parser to check that it never panics or
+//! hangs, and that whatever it rejects comes back as an error pointing inside the input.
+//!
+//! [`fuzz`] tries a whole day at once, and [`fuzz_parser`] one of the parsers it's built
+//! from, on just the kind of text that parser reads.
+//!
+//! Every run is seeded, so a failure can be replayed by setting `FUZZ_SEED` to the seed it
+//! reports. `FUZZ_ITERATIONS` sets how many inputs are tried (500 by default).
+
+use std::env;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::diagnostic::{self, Diagnostic, Span};
+use crate::rng::Rng;
+use crate::solution::{Part, Solution};
+use crate::stress::Generate;
+
+const DEFAULT_ITERATIONS: usize = 500;
+
+// Small inputs are quicker to check and easier to read when one fails
+const MAX_SIZE: usize = 10;
+
+// Far longer than any puzzle input takes to parse, so hitting it means something is stuck
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// Things that tend to upset parsers: separators in the wrong place, numbers that are
+// negative or don't fit in 32 or 64 bits, and characters that aren't one byte long
+const TOKENS: &[&str] = &[
+    "",
+    " ",
+    "  ",
+    "\n",
+    "\n\n",
+    "\r\n",
+    "\t",
+    ",",
+    "-",
+    " -> ",
+    "[",
+    "]",
+    "[]",
+    ":",
+    "$ ",
+    "0",
+    "-1",
+    "4294967296",
+    "18446744073709551616",
+    "é",
+    "\u{feff}",
+];
+
+/// Runs `iterations` rounds, each of which builds an input (either one of `examples` or a
+/// freshly generated one) and checks that it parses, then mangles it and checks that parsing
+/// the result fails cleanly if it fails at all. Generated inputs are solved as well, to make
+/// sure the parts can't be made to panic by an input the parser accepts.
+pub fn fuzz<S: Solution + 'static>(examples: &[&str], generate: Generate) {
+    let (first_seed, iterations) = seeds();
+
+    for example in examples {
+        let untidy = example.replace('\n', "\r\n") + "\r\n  \r\n";
+        if let Err(problem) = check_same_answers::<S>(example, &untidy) {
+            panic!("day {}: {}\ninput: {:?}", S::DAY, problem, untidy);
+        }
+    }
+
+    for seed in first_seed..first_seed.saturating_add(iterations) {
+        let mut rng = Rng::new(seed);
+
+        // The examples already have tests checking their answers
+        let (valid, check): (String, Check) = match examples.is_empty() || rng.one_in(2) {
+            true => {
+                let size = rng.between(1, MAX_SIZE);
+                (generate(&mut rng, size), check_solvable::<S>)
+            }
+            false => (rng.pick(examples).to_string(), check_parses::<S>),
+        };
+        if let Err(problem) = run(valid.clone(), check) {
+            panic!("day {} seed {}: {}\ninput: {:?}", S::DAY, seed, problem, valid);
+        }
+
+        let mutated = mutate(&mut rng, &valid);
+        if let Err(problem) = run(mutated.clone(), check_mutated::<S>) {
+            panic!("day {} seed {}: {}\ninput: {:?}", S::DAY, seed, problem, mutated);
+        }
+    }
+}
+
+/// Checks that `parse` accepts each of `examples`, then throws mangled versions of them at
+/// it, checking that it never panics or hangs and only fails with errors pointing inside the
+/// input. `parse` wraps one of a day's parsers so that it reads text, and `name` says which
+/// one in any failure.
+pub fn fuzz_parser<E: Diagnostic + 'static>(
+    name: &str,
+    examples: &[&str],
+    parse: fn(&str) -> Result<(), E>,
+) {
+    let (first_seed, iterations) = seeds();
+
+    for example in examples {
+        if let Err(err) = parse(example) {
+            let rendered = diagnostic::render(&err, "input", example);
+            panic!("{}: rejected a valid input:\n{}", name, rendered);
+        }
+    }
+
+    for seed in first_seed..first_seed.saturating_add(iterations) {
+        let mut rng = Rng::new(seed);
+        let example = *rng.pick(examples);
+        let mutated = mutate(&mut rng, example);
+
+        let check = move |input: &str| match parse(input) {
+            Ok(()) => Ok(()),
+            Err(err) => check_error(&err, input),
+        };
+        if let Err(problem) = run(mutated.clone(), check) {
+            panic!("{} seed {}: {}\ninput: {:?}", name, seed, problem, mutated);
+        }
+    }
+}
+
+// The first seed to try and how many, from the environment
+fn seeds() -> (u64, u64) {
+    let first_seed = env_number("FUZZ_SEED").unwrap_or(0);
+    let iterations = match env::var_os("FUZZ_SEED") {
+        // Replaying a single failure
+        Some(_) => env_number("FUZZ_ITERATIONS").unwrap_or(1),
+        None => env_number("FUZZ_ITERATIONS").unwrap_or(DEFAULT_ITERATIONS as u64),
+    };
+    (first_seed, iterations)
+}
+
+fn env_number(name: &str) -> Option<u64> {
+    env::var(name).ok()?.parse().ok()
+}
+
+/// Mangles `input` with a few random edits: deleting, inserting and replacing characters,
+/// duplicating, dropping and swapping lines, and cutting it short.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut text = input.to_string();
+
+    for _ in 0..rng.between(1, 3) {
+        let mut chars = text.chars().collect::<Vec<char>>();
+        let mut lines = text.split('\n').map(String::from).collect::<Vec<String>>();
+
+        match rng.below(8) {
+            0 if !chars.is_empty() => {
+                let start = rng.below(chars.len());
+                let end = (start + rng.between(1, 4)).min(chars.len());
+                chars.drain(start..end);
+            }
+            1 => {
+                let at = rng.below(chars.len() + 1);
+                let token = rng.pick(TOKENS);
+                chars.splice(at..at, token.chars());
+            }
+            2 if !chars.is_empty() => {
+                let at = rng.below(chars.len());
+                chars[at] = *rng.pick(&chars);
+            }
+            3 => {
+                let line = rng.below(lines.len());
+                lines.insert(line, lines[line].clone());
+            }
+            4 => {
+                lines.remove(rng.below(lines.len()));
+            }
+            5 => {
+                let (a, b) = (rng.below(lines.len()), rng.below(lines.len()));
+                lines.swap(a, b);
+            }
+            6 => chars.truncate(rng.below(chars.len() + 1)),
+            _ => {
+                // Swap one of the numbers for one that's out of range
+                let digits = chars.iter().filter(|c| c.is_ascii_digit()).count();
+                if digits > 0 {
+                    let nth = rng.below(digits);
+                    let mut positions = (0..chars.len()).filter(|&i| chars[i].is_ascii_digit());
+                    let at = positions.nth(nth).unwrap_or(0);
+                    let number = *rng.pick(&["0", "-1", "4294967296", "18446744073709551616"]);
+                    chars.splice(at..at + 1, number.chars());
+                }
+            }
+        }
+
+        // Each edit works either on characters or on lines, whichever it changed wins
+        text = match chars.iter().copied().eq(text.chars()) {
+            true => lines.join("\n"),
+            false => chars.into_iter().collect(),
+        };
+    }
+
+    text
+}
+
+type Check = fn(&str) -> Result<(), String>;
+
+// Runs `check` on another thread so that a panic or a hang can be reported instead of
+// taking down (or stalling) the whole test run
+fn run(
+    input: String,
+    check: impl FnOnce(&str) -> Result<(), String> + Send + 'static,
+) -> Result<(), String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(check(&input));
+    });
+
+    match receiver.recv_timeout(TIMEOUT) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(format!("still running after {:?}", TIMEOUT)),
+        Err(RecvTimeoutError::Disconnected) => Err("panicked".to_string()),
+    }
+}
+
+fn check_parses<S: Solution>(input: &str) -> Result<(), String> {
+    parse_valid::<S>(input).map(|_| ())
+}
+
+// The parts may still fail on a valid input (e.g. a heightmap with no path to the end),
+// but they have to do so with an error rather than a panic
+fn check_solvable<S: Solution>(input: &str) -> Result<(), String> {
+    let parsed = parse_valid::<S>(input)?;
+    for part in Part::BOTH {
+        if let Err(err) = S::solve(part, &parsed) {
+            check_error(&err, input)?;
+        }
+    }
+    Ok(())
+}
+
+// Line endings and blank lines at the end are up to whatever saved the input, so they
+// mustn't change the answers
+fn check_same_answers<S: Solution>(input: &str, untidy: &str) -> Result<(), String> {
+    let answers = |input: &str| match S::parse(input) {
+        Ok(parsed) => Part::BOTH.map(|part| S::solve(part, &parsed).map_err(|err| err.to_string())),
+        Err(err) => [Err(err.to_string()), Err(String::new())],
+    };
+    match answers(untidy) == answers(input) {
+        true => Ok(()),
+        false => Err(String::from("changing the line endings changed the answers")),
+    }
+}
+
+fn parse_valid<S: Solution>(input: &str) -> Result<S::Input, String> {
+    S::parse(input).map_err(|err| {
+        format!("rejected a valid input:\n{}", diagnostic::render(&err, "input", input))
+    })
+}
+
+fn check_mutated<S: Solution>(input: &str) -> Result<(), String> {
+    match S::parse(input) {
+        Ok(_) => Ok(()),
+        Err(err) => check_error(&err, input),
+    }
+}
+
+fn check_error(error: &dyn Diagnostic, input: &str) -> Result<(), String> {
+    diagnostic::render(error, "input", input);
+
+    match error.span() {
+        Some(span) if !span_within(span, input) => {
+            Err(format!("error `{}` points outside the input at {:?}", error, span))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Spans may point one character past the end of a line, or at the empty line after the last
+// one, for things missing from the input
+fn span_within(span: Span, input: &str) -> bool {
+    let lines = input.lines().collect::<Vec<&str>>();
+    let line = match span.line.checked_sub(1) {
+        Some(i) if i < lines.len() => lines[i],
+        Some(i) if i == lines.len() => "",
+        _ => return false,
+    };
+    span.column >= 1 && span.column + span.len <= line.chars().count() + 2
+}
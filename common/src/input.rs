@@ -0,0 +1,175 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+/// Where a day's puzzle input should be read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// Either a single input file, or a directory holding the inputs of several days.
+    Path(PathBuf),
+    Stdin,
+}
+
+/// The text of a puzzle input along with where it came from, so that errors can point into it.
+/// The text has been through [`normalise`].
+#[derive(Debug, Clone)]
+pub struct PuzzleInput {
+    pub name: String,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// No input was given on the command line and nothing is being piped in.
+    Missing { day: u8 },
+    /// The input directory doesn't contain an input for this day.
+    NotFound { day: u8, tried: Vec<PathBuf> },
+    Io { path: PathBuf, source: io::Error },
+    Stdin(io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Missing { day } => write!(
+                f,
+                "no input for day {}: pass an input file, a directory of inputs, or '-' to read from stdin",
+                day
+            ),
+            InputError::NotFound { day, tried } => {
+                write!(f, "no input for day {} found, tried:", day)?;
+                for path in tried {
+                    write!(f, "\n    {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Io { path, source } => {
+                write!(f, "could not read '{}': {}", path.display(), source)
+            }
+            InputError::Stdin(source) => write!(f, "could not read stdin: {}", source),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Io { source, .. } | InputError::Stdin(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl PuzzleInput {
+    pub fn checksum(&self) -> u64 {
+        checksum(&self.text)
+    }
+}
+
+impl Source {
+    /// Interprets a command line argument as an input source. A lone '-' means
+    /// stdin, and with no argument at all we only fall back to stdin when
+    /// something is actually being piped in.
+    pub fn from_arg(day: u8, arg: Option<&str>) -> Result<Self, InputError> {
+        match arg {
+            Some("-") => Ok(Source::Stdin),
+            Some(path) => Ok(Source::Path(PathBuf::from(path))),
+            None if !io::stdin().is_terminal() => Ok(Source::Stdin),
+            None => Err(InputError::Missing { day }),
+        }
+    }
+
+    pub fn load(&self, day: u8) -> Result<PuzzleInput, InputError> {
+        match self {
+            Source::Path(path) => {
+                let path = resolve(day, path)?;
+                Ok(PuzzleInput {
+                    name: path.display().to_string(),
+                    text: normalise(&read_file(&path)?),
+                })
+            }
+            Source::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(InputError::Stdin)?;
+                Ok(PuzzleInput {
+                    name: String::from("<stdin>"),
+                    text: normalise(&text),
+                })
+            }
+        }
+    }
+
+    /// Opens the input for `day` to be read a bit at a time, for inputs too large to load,
+    /// and returns its name along with it. Unlike [`load`](Self::load) nothing is normalised,
+    /// so whatever reads it has to cope with `\r\n` and blank lines at the end itself.
+    pub fn open(&self, day: u8) -> Result<(String, Box<dyn BufRead>), InputError> {
+        match self {
+            Source::Path(path) => {
+                let path = resolve(day, path)?;
+                let file = fs::File::open(&path).map_err(|source| InputError::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+                Ok((path.display().to_string(), Box::new(BufReader::new(file))))
+            }
+            Source::Stdin => Ok((String::from("<stdin>"), Box::new(io::stdin().lock()))),
+        }
+    }
+}
+
+/// Finds the input file for `day` under `path`. Files are used as they are, while
+/// directories may either hold flat `dayN.txt` files or mirror this repository's
+/// `dayN/src/input.txt` layout.
+pub fn resolve(day: u8, path: &Path) -> Result<PathBuf, InputError> {
+    if !path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+
+    let candidates = vec![
+        path.join(format!("day{}.txt", day)),
+        path.join(format!("day{}", day)).join("src").join("input.txt"),
+    ];
+
+    match candidates.iter().find(|candidate| candidate.is_file()) {
+        Some(candidate) => Ok(candidate.clone()),
+        None => Err(InputError::NotFound {
+            day,
+            tried: candidates,
+        }),
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    fs::read_to_string(path).map_err(|source| InputError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Evens out the differences between copies of the same input: a byte order mark at the
+/// start, Windows line endings, and however many blank lines and spaces there are at the
+/// end. What's left ends with a single newline, unless it's empty.
+///
+/// Whitespace within a line is left alone, since some puzzles line things up with it.
+pub fn normalise(text: &str) -> String {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut normalised = text.replace("\r\n", "\n").trim_end().to_string();
+    if !normalised.is_empty() {
+        normalised.push('\n');
+    }
+    normalised
+}
+
+/// A 64-bit FNV-1a hash of an input. It's no use against tampering, but it's plenty to tell
+/// puzzle inputs apart and it stays the same between Rust versions, unlike `DefaultHasher`.
+pub fn checksum(input: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    input.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
@@ -0,0 +1,19 @@
+pub mod diagnostic;
+pub mod explain;
+#[cfg(feature = "stress")]
+pub mod fuzz;
+pub mod input;
+pub mod output;
+pub mod parse;
+#[cfg(feature = "stress")]
+pub mod rng;
+pub mod run;
+pub mod solution;
+#[cfg(feature = "stress")]
+pub mod stress;
+pub mod visual;
+
+pub use diagnostic::{Diagnostic, Span};
+#[cfg(feature = "stress")]
+pub use rng::Rng;
+pub use solution::{Answer, Part, Solution};
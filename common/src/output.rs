@@ -0,0 +1,206 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::diagnostic::{self, Diagnostic};
+use crate::explain::Note;
+use crate::input::PuzzleInput;
+use crate::run::Solved;
+use crate::solution::{Answer, Part};
+
+/// How results are printed: as text for people, or as JSON for scripts to pick up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// What became of one part of a day. A day whose input couldn't be parsed has a single
+/// record without a part, since neither part got anywhere.
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: Option<Part>,
+    pub input: &'a PuzzleInput,
+    pub outcome: Result<&'a Answer, &'a dyn Diagnostic>,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+}
+
+/// The records for solving `day` on `input`, one per part that was solved.
+pub fn records<'a>(
+    day: u8,
+    input: &'a PuzzleInput,
+    solved: &'a Result<Solved, Box<dyn Diagnostic>>,
+) -> Vec<Record<'a>> {
+    match solved {
+        Ok(solved) => solved
+            .parts
+            .iter()
+            .map(|part| Record {
+                day,
+                part: Some(part.part),
+                input,
+                outcome: part.answer.as_ref().map_err(|err| &**err),
+                parse_time: Some(solved.parse_time),
+                solve_time: Some(part.time),
+            })
+            .collect(),
+        Err(err) => vec![Record {
+            day,
+            part: None,
+            input,
+            outcome: Err(&**err),
+            parse_time: None,
+            solve_time: None,
+        }],
+    }
+}
+
+impl Record<'_> {
+    /// Prints the answer after `indent` on stdout, or renders the error on stderr.
+    pub fn print_text(&self, indent: &str) {
+        match (self.outcome, self.part) {
+            (Ok(answer), Some(part)) => println!("{}{}", indent, text(part, answer)),
+            (Ok(answer), None) => println!("{}{}", indent, answer),
+            (Err(err), _) => {
+                eprint!("{}", diagnostic::render(err, &self.input.name, &self.input.text))
+            }
+        }
+    }
+
+    /// The record as a single line of JSON. Answers are always strings, with `type` saying
+    /// how to read them, so that large numbers survive tools that only have doubles.
+    /// Durations are in whole nanoseconds, and `null` when that step wasn't reached.
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\"day\":{},\"part\":{}", self.day, number(self.part));
+
+        match self.outcome {
+            Ok(answer) => {
+                let _ = write!(
+                    json,
+                    ",\"answer\":\"{}\",\"type\":\"{}\"",
+                    escape_json(&answer.to_string()),
+                    answer.kind()
+                );
+            }
+            Err(err) => {
+                let span = err.span();
+                let _ = write!(
+                    json,
+                    ",\"error\":\"{}\",\"line\":{},\"column\":{}",
+                    escape_json(&err.to_string()),
+                    number(span.map(|span| span.line)),
+                    number(span.map(|span| span.column))
+                );
+            }
+        }
+
+        let _ = write!(
+            json,
+            ",\"input\":\"{}\",\"input_checksum\":\"{:016x}\",\"parse_ns\":{},\"solve_ns\":{}}}",
+            escape_json(&self.input.name),
+            self.input.checksum(),
+            number(self.parse_time.map(|time| time.as_nanos())),
+            number(self.solve_time.map(|time| time.as_nanos()))
+        );
+        json
+    }
+}
+
+fn number(value: Option<impl std::fmt::Display>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("null"),
+    }
+}
+
+/// Prints what a day explained on its way to the answers, grouped by the step each note was
+/// left in.
+pub fn print_notes_text(notes: &[Note], indent: &str) {
+    let mut step = None;
+    for (i, note) in notes.iter().enumerate() {
+        if i == 0 || note.part != step {
+            match note.part {
+                Some(part) => println!("{}Explaining part {}:", indent, part),
+                None => println!("{}Explaining the parse:", indent),
+            }
+            step = note.part;
+        }
+        println!("{}  {} {}", indent, note.topic, note.detail);
+    }
+}
+
+/// A note as a single line of JSON, with the same `day` and `part` as the answers. Its
+/// fields are an object of strings, like answers, e.g. `"fields":{"elf":"4"}`.
+pub fn note_json(day: u8, note: &Note) -> String {
+    let mut json = format!(
+        "{{\"day\":{},\"part\":{},\"topic\":\"{}\",\"note\":\"{}\",\"fields\":{{",
+        day,
+        number(note.part),
+        escape_json(note.topic),
+        escape_json(&note.detail)
+    );
+    for (i, (name, value)) in note.fields.iter().enumerate() {
+        let comma = if i == 0 { "" } else { "," };
+        let _ = write!(json, "{}\"{}\":\"{}\"", comma, escape_json(name), escape_json(value));
+    }
+    json.push_str("}}");
+    json
+}
+
+/// An answer labelled with its part, e.g. `Part 1: 24000`. Screens start on their own line.
+pub fn text(part: Part, answer: &Answer) -> String {
+    match answer {
+        Answer::Screen(screen) => format!("Part {}:\n{}", part, screen.trim_end()),
+        answer => format!("Part {}: {}", part, answer),
+    }
+}
+
+pub fn escape_json(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_put_their_fields_in_an_object() {
+        let note = Note {
+            part: Some(Part::Two),
+            topic: "pair",
+            detail: String::from("3 is in the \"wrong\" order"),
+            fields: vec![("pair", String::from("3")), ("verdict", String::from("wrong"))],
+        };
+        assert_eq!(
+            note_json(13, &note),
+            "{\"day\":13,\"part\":2,\"topic\":\"pair\",\
+             \"note\":\"3 is in the \\\"wrong\\\" order\",\
+             \"fields\":{\"pair\":\"3\",\"verdict\":\"wrong\"}}"
+        );
+
+        let note = Note { part: None, fields: Vec::new(), ..note };
+        assert!(note_json(13, &note).ends_with(",\"fields\":{}}"));
+    }
+}
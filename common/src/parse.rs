@@ -0,0 +1,430 @@
+//! Small building blocks for the puzzle formats, which are all made of lines: words,
+//! numbers and fixed bits of text, lists of them with separators, and blocks of lines with
+//! blank lines in between.
+//!
+//! A [`Cursor`] reads one line from left to right. Each of its methods either consumes what
+//! it was asked for or fails with an [`Error`] pointing at what it found instead, leaving
+//! the cursor where it was so something else can be tried.
+
+use std::any;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::diagnostic::{Diagnostic, Span};
+
+/// One line of the input without its line ending. Lines are numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// The span of the whole line.
+    pub fn span(&self) -> Span {
+        Span::line(self.number, self.text)
+    }
+
+    /// The span of `token`, which has to be a slice of this line.
+    pub fn span_of(&self, token: &str) -> Span {
+        Span::of(self.number, self.text, token)
+    }
+
+    /// Points just past the end of the line, for things that are missing from it.
+    pub fn end(&self) -> Span {
+        Span::end_of(self.number, self.text)
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    pub fn cursor(&self) -> Cursor<'a> {
+        Cursor { line: *self, offset: 0 }
+    }
+}
+
+/// Every line of `input`, numbered, whether it ends in `\n` or `\r\n`. Blank lines at the end
+/// are left out, as they're only there because of however the input was saved.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    let lines = input.lines().collect::<Vec<&str>>();
+    let end = lines.iter().rposition(|text| !text.trim().is_empty()).map_or(0, |i| i + 1);
+    lines.into_iter().take(end).enumerate().map(|(i, text)| Line { number: i + 1, text })
+}
+
+/// The runs of lines between blank lines, e.g. the elves in day 1. Any number of blank lines
+/// separates two blocks, so none of them are empty.
+pub fn blocks(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut blocks = vec![Vec::new()];
+    for line in lines(input) {
+        match line.is_blank() {
+            true => blocks.push(Vec::new()),
+            false => blocks.last_mut().unwrap().push(line),
+        }
+    }
+    blocks.retain(|block| !block.is_empty());
+    blocks
+}
+
+/// What a [`Cursor`] was looking for when it failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Literal(&'static str),
+    OneOf(Vec<&'static str>),
+    Token,
+    Integer,
+    /// Digits were found but they don't make a number of the named type.
+    IntegerFitting(&'static str),
+    End,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Literal(literal) => write!(f, "`{}`", literal),
+            Expected::OneOf(options) => {
+                for (i, option) in options.iter().enumerate() {
+                    match i {
+                        0 => (),
+                        i if i == options.len() - 1 => write!(f, " or ")?,
+                        _ => write!(f, ", ")?,
+                    }
+                    write!(f, "`{}`", option)?;
+                }
+                Ok(())
+            }
+            Expected::Token => write!(f, "a word"),
+            Expected::Integer => write!(f, "a number"),
+            Expected::IntegerFitting(name) => write!(f, "a number that fits in a `{}`", name),
+            Expected::End => write!(f, "the end of the line"),
+        }
+    }
+}
+
+/// Something a [`Cursor`] didn't find, and what was there instead. `found` is empty at the
+/// end of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub span: Span,
+    pub expected: Expected,
+    pub found: String,
+}
+
+impl Error {
+    pub fn at_end(&self) -> bool {
+        self.found.is_empty()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.at_end() {
+            true => write!(f, "expected {}, found the end of the line", self.expected),
+            false => write!(f, "expected {}, found `{}`", self.expected, self.found),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        Some(self.span)
+    }
+}
+
+/// Reads a [`Line`] from left to right.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    line: Line<'a>,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn line(&self) -> Line<'a> {
+        self.line
+    }
+
+    /// Everything that hasn't been read yet.
+    pub fn rest(&self) -> &'a str {
+        &self.line.text[self.offset..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// An error saying `expected` isn't next, pointing at what is instead: the next run of
+    /// letters and digits, or a single character of anything else, after any whitespace.
+    pub fn error(&self, expected: Expected) -> Error {
+        let rest = match self.rest().trim_start() {
+            "" => self.rest(),
+            rest => rest,
+        };
+        let len = match rest.chars().next() {
+            Some(c) if c.is_alphanumeric() => {
+                rest.find(|c: char| !c.is_alphanumeric()).unwrap_or(rest.len())
+            }
+            Some(c) => c.len_utf8(),
+            None => 0,
+        };
+        self.error_at(expected, &rest[..len])
+    }
+
+    // `found` has to be a slice of the line
+    fn error_at(&self, expected: Expected, found: &str) -> Error {
+        Error {
+            span: match found.is_empty() {
+                true => self.line.end(),
+                false => self.line.span_of(found),
+            },
+            expected,
+            found: found.to_string(),
+        }
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let taken = &self.rest()[..len];
+        self.offset += len;
+        taken
+    }
+
+    /// Consumes characters for as long as `pred` holds, which may be none at all.
+    pub fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let len = self.rest().find(|c| !pred(c)).unwrap_or(self.rest().len());
+        self.advance(len)
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    /// Consumes `literal` if it's next, and says whether it was.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        match self.rest().starts_with(literal) {
+            true => {
+                self.advance(literal.len());
+                true
+            }
+            false => false,
+        }
+    }
+
+    /// Consumes `literal`, which has to be next.
+    pub fn literal(&mut self, literal: &'static str) -> Result<(), Error> {
+        match self.eat(literal) {
+            true => Ok(()),
+            false => Err(self.error(Expected::Literal(literal))),
+        }
+    }
+
+    /// The next word, skipping any whitespace before it like `split_whitespace` does.
+    pub fn token(&mut self) -> Result<&'a str, Error> {
+        let start = self.offset;
+        self.skip_whitespace();
+        match self.take_while(|c| !c.is_whitespace()) {
+            "" => {
+                let error = self.error(Expected::Token);
+                self.offset = start;
+                Err(error)
+            }
+            token => Ok(token),
+        }
+    }
+
+    /// The next word, which has to be one of the `options` and stands for the value next to
+    /// it.
+    pub fn one_of<T: Clone>(&mut self, options: &[(&'static str, T)]) -> Result<T, Error> {
+        let start = self.offset;
+        self.skip_whitespace();
+        let token = self.take_while(|c| !c.is_whitespace());
+        let error = match options.iter().find(|(option, _)| *option == token) {
+            Some((_, value)) => return Ok(value.clone()),
+            None => self.error_at(Expected::OneOf(options.iter().map(|o| o.0).collect()), token),
+        };
+        self.offset = start;
+        Err(error)
+    }
+
+    /// A whole number, with a `-` in front if it's negative.
+    pub fn integer<T: FromStr>(&mut self) -> Result<T, Error> {
+        let sign = usize::from(self.rest().starts_with('-'));
+        let digits = self.rest()[sign..].find(|c: char| !c.is_ascii_digit());
+        let len = sign + digits.unwrap_or(self.rest().len() - sign);
+
+        // Nothing is consumed until the number is known to be good
+        if len == sign {
+            return Err(self.error(Expected::Integer));
+        }
+        let text = &self.rest()[..len];
+        match text.parse() {
+            Ok(number) => {
+                self.advance(len);
+                Ok(number)
+            }
+            Err(_) => Err(self.error_at(Expected::IntegerFitting(any::type_name::<T>()), text)),
+        }
+    }
+
+    /// One or more items with `separator` between them. If any item fails, the whole list
+    /// does, separators and all.
+    pub fn separated<T, E>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, E>,
+    ) -> Result<Vec<T>, E> {
+        let start = self.offset;
+        let mut items = Vec::new();
+        loop {
+            match item(self) {
+                Ok(value) => items.push(value),
+                Err(err) => {
+                    self.offset = start;
+                    return Err(err);
+                }
+            }
+            if !self.eat(separator) {
+                return Ok(items);
+            }
+        }
+    }
+
+    /// Runs `parse` and returns what it read along with the text it consumed.
+    pub fn consumed<T, E>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, E>,
+    ) -> Result<(T, &'a str), E> {
+        let start = self.offset;
+        match parse(self) {
+            Ok(value) => Ok((value, &self.line.text[start..self.offset])),
+            Err(err) => {
+                self.offset = start;
+                Err(err)
+            }
+        }
+    }
+
+    /// Checks that the whole line has been read.
+    pub fn end(&self) -> Result<(), Error> {
+        match self.is_at_end() {
+            true => Ok(()),
+            false => Err(self.error(Expected::End)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(text: &str) -> Line<'_> {
+        Line { number: 3, text }
+    }
+
+    #[test]
+    fn spans_count_characters_from_1() {
+        let line = line("héllo wörld");
+        let mut cursor = line.cursor();
+        cursor.token().unwrap();
+        let err = cursor.literal("!").unwrap_err();
+        assert_eq!(err.span, Span { line: 3, column: 7, len: 5 });
+        assert_eq!(err.found, "wörld");
+
+        cursor.token().unwrap();
+        let err = cursor.token().unwrap_err();
+        assert_eq!(err.span, Span { line: 3, column: 12, len: 1 });
+        assert!(err.at_end());
+    }
+
+    #[test]
+    fn integers_can_be_negative_but_have_to_fit() {
+        let line = line("-12 -x 300 99999999999");
+        let mut cursor = line.cursor();
+        assert_eq!(cursor.integer::<i32>(), Ok(-12));
+        cursor.skip_whitespace();
+
+        let err = cursor.integer::<i32>().unwrap_err();
+        assert_eq!((err.expected, err.found.as_str()), (Expected::Integer, "-"));
+        assert_eq!(cursor.rest(), "-x 300 99999999999");
+        cursor.token().unwrap();
+        cursor.skip_whitespace();
+
+        let err = cursor.integer::<u8>().unwrap_err();
+        assert_eq!(err.expected, Expected::IntegerFitting("u8"));
+        assert_eq!((err.found.as_str(), err.span.column, err.span.len), ("300", 8, 3));
+        assert_eq!(cursor.rest(), "300 99999999999");
+
+        assert_eq!(cursor.integer::<u16>(), Ok(300));
+        cursor.skip_whitespace();
+
+        let err = cursor.integer::<i32>().unwrap_err();
+        assert_eq!(err.expected, Expected::IntegerFitting("i32"));
+        assert_eq!(cursor.integer::<i64>(), Ok(99_999_999_999));
+        assert!(cursor.is_at_end());
+    }
+
+    #[test]
+    fn failing_leaves_the_cursor_where_it_was() {
+        let line = line("  north 12");
+        let mut cursor = line.cursor();
+        let directions = [("east", 0), ("west", 1)];
+        let err = cursor.one_of(&directions).unwrap_err();
+        assert_eq!(err.expected, Expected::OneOf(vec!["east", "west"]));
+        assert_eq!(err.found, "north");
+        assert_eq!(cursor.rest(), "  north 12");
+
+        assert_eq!(cursor.one_of(&[("north", 'N')]), Ok('N'));
+        assert_eq!(cursor.rest(), " 12");
+
+        let mut cursor = line.cursor();
+        cursor.token().unwrap();
+        cursor.token().unwrap();
+        cursor.eat(" ");
+        assert!(cursor.token().is_err());
+        assert!(cursor.is_at_end());
+
+        let line = Line { number: 1, text: "   " };
+        let mut cursor = line.cursor();
+        assert!(cursor.token().is_err());
+        assert_eq!(cursor.rest(), "   ");
+    }
+
+    #[test]
+    fn separated_items_fail_as_a_whole() {
+        let line = line("1,2,3;4,x");
+        let mut cursor = line.cursor();
+        assert_eq!(cursor.separated(",", Cursor::integer::<u8>), Ok(vec![1, 2, 3]));
+        assert_eq!(cursor.rest(), ";4,x");
+
+        cursor.literal(";").unwrap();
+        let err = cursor.separated(",", Cursor::integer::<u8>).unwrap_err();
+        assert_eq!(err.found, "x");
+        assert_eq!(cursor.rest(), "4,x");
+
+        let err = cursor.consumed(|cursor| cursor.separated(",", Cursor::integer::<u8>));
+        assert!(err.is_err());
+        assert_eq!(cursor.rest(), "4,x");
+        let (four, text) = cursor.consumed(Cursor::integer::<u8>).unwrap();
+        assert_eq!((four, text), (4, "4"));
+    }
+
+    #[test]
+    fn lines_leave_out_blank_lines_at_the_end() {
+        let lines = lines("a\r\n\nb\n  \n\n").collect::<Vec<_>>();
+        let texts = lines.iter().map(|line| (line.number, line.text)).collect::<Vec<_>>();
+        assert_eq!(texts, vec![(1, "a"), (2, ""), (3, "b")]);
+
+        assert_eq!(super::lines("\n\n").count(), 0);
+        assert_eq!(super::lines("").count(), 0);
+    }
+
+    #[test]
+    fn blocks_are_split_by_any_number_of_blank_lines() {
+        let blocks = blocks("\n1\n2\n\n\n \n3\n\n4\n\n");
+        let blocks = blocks
+            .iter()
+            .map(|block| block.iter().map(|line| (line.number, line.text)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, vec![vec![(2, "1"), (3, "2")], vec![(7, "3")], vec![(9, "4")]]);
+    }
+}
@@ -0,0 +1,133 @@
+use std::env;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::diagnostic::Diagnostic;
+use crate::explain::{self, Note};
+use crate::input::Source;
+use crate::output::{self, Format};
+use crate::solution::{Answer, Part, Solution};
+
+/// Any day's error, so that days with different error types can be handled together.
+pub type Failure = Box<dyn Diagnostic>;
+
+/// One part's answer, or why there isn't one, along with how long solving it took.
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Result<Answer, Failure>,
+    pub time: Duration,
+}
+
+/// A parsed input along with the result of each part that was asked for, in the same order.
+pub struct Solved {
+    pub parse_time: Duration,
+    pub parts: Vec<PartResult>,
+    /// Whatever the day explained along the way, when explaining is turned on
+    pub notes: Vec<Note>,
+}
+
+/// Parses the input once and then solves each part in turn, timing each step. Parsing
+/// failures fail the whole day, while each part can fail on its own.
+pub fn solve<S: Solution>(input: &str, parts: &[Part]) -> Result<Solved, Failure> {
+    // Notes left before now don't belong to this input
+    explain::take();
+
+    let start = Instant::now();
+    let parsed = S::parse(input).map_err(|err| Box::new(err) as Failure)?;
+    let parse_time = start.elapsed();
+    let mut notes = explain::take();
+
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = S::solve(part, &parsed).map_err(|err| Box::new(err) as Failure);
+            let time = start.elapsed();
+
+            notes.extend(explain::take().into_iter().map(|note| Note { part: Some(part), ..note }));
+            PartResult { part, answer, time }
+        })
+        .collect();
+
+    Ok(Solved { parse_time, parts, notes })
+}
+
+/// The whole of a day's binary: reads the input named on the command line (or piped in)
+/// and prints the answer to both parts, and how it got there with `--explain`.
+pub fn main<S: Solution>() -> ExitCode {
+    let usage = format!(
+        "Usage:\n    day{} [<PATH> | -] [--format <text|json>] [--explain]",
+        S::DAY
+    );
+
+    let Args { path, format, explain } = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, usage);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let source = Source::from_arg(S::DAY, path.as_deref());
+    let input = match source.and_then(|source| source.load(S::DAY)) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if explain {
+        explain::enable();
+    }
+    let solved = solve::<S>(&input.text, &Part::BOTH);
+
+    let mut succeeded = true;
+    for record in output::records(S::DAY, &input, &solved) {
+        succeeded &= record.outcome.is_ok();
+        match format {
+            Format::Text => record.print_text(""),
+            Format::Json => println!("{}", record.to_json()),
+        }
+    }
+    if let Ok(solved) = &solved {
+        match format {
+            Format::Text => output::print_notes_text(&solved.notes, ""),
+            Format::Json => solved.notes.iter().for_each(|note| {
+                println!("{}", output::note_json(S::DAY, note));
+            }),
+        }
+    }
+
+    match succeeded {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+struct Args {
+    path: Option<String>,
+    format: Format,
+    explain: bool,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut path = None;
+    let mut format = Format::Text;
+    let mut explain = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                let name = args.next().unwrap_or_default();
+                format = Format::from_name(&name)
+                    .ok_or_else(|| String::from("'--format' expects 'text' or 'json'"))?;
+            }
+            "--explain" => explain = true,
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    Ok(Args { path, format, explain })
+}
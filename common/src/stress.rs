@@ -0,0 +1,71 @@
+//! Checks a day's solver against a slow but obviously correct one on generated inputs.
+//!
+//! Each day has a `stress` module with a `generate` function, which writes a random input in
+//! the puzzle's exact format from a seed and a size, and a `brute_force` function, which
+//! solves an input the long way round. The two solvers agreeing on inputs far larger than
+//! the real one is good evidence that the fast one is right, and that it scales.
+
+use crate::rng::Rng;
+use crate::run::{self, Failure, Solved};
+use crate::solution::{Answer, Part, Solution};
+
+/// Writes an input of roughly `size` records (elves, moves, trees along a side, ...).
+pub type Generate = fn(&mut Rng, usize) -> String;
+
+/// The answer to each part, or `None` where the input has no answer (e.g. a heightmap
+/// without a path to the end) and the solver should fail. It only has to cope with inputs
+/// the day's generator writes, so it can panic on anything else.
+pub type BruteForce = fn(&str) -> [Option<Answer>; 2];
+
+const TEST_SEEDS: u64 = 20;
+
+/// Checks that the solver agrees with `brute_force` on inputs generated from the first few
+/// seeds at the given size, panicking with the seed and input of the first disagreement.
+pub fn cross_check<S: Solution>(generate: Generate, brute_force: BruteForce, size: usize) {
+    for seed in 0..TEST_SEEDS {
+        let input = generate(&mut Rng::new(seed), size);
+        let solved = run::solve::<S>(&input, &Part::BOTH);
+        if let Err(problem) = compare(&solved, &brute_force(&input)) {
+            panic!("day {} seed {} size {}: {}\ninput: {:?}", S::DAY, seed, size, problem, input);
+        }
+    }
+}
+
+/// Where the solver's results differ from what the brute force solver expected, if anywhere.
+pub fn compare(
+    solved: &Result<Solved, Failure>,
+    expected: &[Option<Answer>; 2],
+) -> Result<(), String> {
+    let solved = match solved {
+        Ok(solved) => solved,
+        Err(err) => return Err(format!("the input was rejected: {}", err)),
+    };
+
+    for result in &solved.parts {
+        let part = result.part;
+        let expected = &expected[usize::from(part.number()) - 1];
+        match (&result.answer, expected) {
+            (Ok(answer), Some(expected)) if answer == expected => {}
+            (Err(_), None) => {}
+            (Ok(answer), Some(expected)) => {
+                return Err(format!(
+                    "part {} is {:?} but the brute force solver got {:?}",
+                    part, answer, expected
+                ))
+            }
+            (Ok(answer), None) => {
+                return Err(format!(
+                    "part {} is {:?} but the brute force solver found no answer",
+                    part, answer
+                ))
+            }
+            (Err(err), Some(expected)) => {
+                return Err(format!(
+                    "part {} failed with `{}` but the brute force solver got {:?}",
+                    part, err, expected
+                ))
+            }
+        }
+    }
+    Ok(())
+}
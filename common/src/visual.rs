@@ -0,0 +1,301 @@
+//! Plays the step-wise simulations of a day in the terminal, or writes them out as
+//! numbered text frames.
+//!
+//! A day that implements [`Visualise`] turns its input into [`Frame`]s, one per step, and
+//! [`show`] takes care of the rest. While playing, the controls are read a line at a time
+//! from stdin, since that's all a terminal hands over without being switched to raw mode.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::run::Failure;
+use crate::solution::{Part, Solution};
+
+// Terminals can't redraw a frame much more often than this, so frames are skipped instead
+// when playing any faster
+const MAX_REDRAWS_PER_SECOND: f64 = 60.0;
+
+/// The slowest and fastest frames can be shown, in frames a second. Changing the speed while
+/// playing stops at these.
+pub const MIN_SPEED: f64 = 0.1;
+pub const MAX_SPEED: f64 = 10_000.0;
+
+const CONTROLS: &str = "Enter: pause/step  c: continue  +/-: speed  q: quit";
+
+/// A colour from the basic ANSI palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    // The SGR code that switches the text to this colour
+    fn code(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 97,
+            Colour::Grey => 90,
+        }
+    }
+}
+
+/// One picture of a simulation: a rectangle of characters, each in a colour or the
+/// terminal's own, with a caption underneath.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    width: usize,
+    cells: Vec<(char, Option<Colour>)>,
+    pub caption: String,
+}
+
+impl Frame {
+    /// A frame of the given size filled with spaces.
+    pub fn new(width: usize, height: usize) -> Frame {
+        Frame {
+            width,
+            cells: vec![(' ', None); width * height],
+            caption: String::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    /// Draws `c` at column `x` of row `y`. Anything outside the frame is left out, so
+    /// callers can draw a window onto something larger without clipping it first.
+    pub fn draw(&mut self, x: usize, y: usize, c: char, colour: Option<Colour>) {
+        if x < self.width && y < self.height() {
+            self.cells[y * self.width + x] = (c, colour);
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[(char, Option<Colour>)]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The frame as plain text, one line per row and then the caption.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for row in self.rows() {
+            text.extend(row.iter().map(|&(c, _)| c));
+            text.push('\n');
+        }
+        text + &self.caption + "\n"
+    }
+
+    /// The frame with ANSI escape codes for its colours. Each line clears whatever was left
+    /// on it from the frame before.
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.rows() {
+            let mut current = None;
+            for &(c, colour) in row {
+                if colour != current {
+                    match colour {
+                        Some(colour) => text += &format!("\x1b[{}m", colour.code()),
+                        None => text += "\x1b[0m",
+                    }
+                    current = colour;
+                }
+                text.push(c);
+            }
+            if current.is_some() {
+                text += "\x1b[0m";
+            }
+            text += "\x1b[K\n";
+        }
+        text + &self.caption + "\x1b[K\n"
+    }
+}
+
+/// How frames are shown.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// How many frames are shown each second
+    pub speed: f64,
+    /// Start paused, waiting for Enter before each frame
+    pub step: bool,
+    pub colour: bool,
+    /// Write the frames to numbered files in this directory instead of playing them
+    pub dump: Option<PathBuf>,
+}
+
+impl Default for Options {
+    /// Ten frames a second, in colour unless stdout isn't a terminal or `NO_COLOR` is set.
+    fn default() -> Options {
+        Options {
+            speed: 10.0,
+            step: false,
+            colour: io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+            dump: None,
+        }
+    }
+}
+
+/// A day whose solution can be watched one step at a time.
+pub trait Visualise: Solution {
+    /// The frames of solving `part`, one for each step.
+    fn frames(
+        input: &Self::Input,
+        part: Part,
+    ) -> Result<Box<dyn Iterator<Item = Frame> + '_>, Self::Error>;
+}
+
+/// Shows a day's frames for any input, so that days can be handled together.
+pub type Visualiser = fn(&str, Part, &Options) -> Result<usize, Failure>;
+
+/// Parses `input` and shows the frames of solving `part`. Returns how many frames were
+/// shown, which is fewer than there are when playing is stopped early.
+pub fn visualise<S: Visualise>(
+    input: &str,
+    part: Part,
+    options: &Options,
+) -> Result<usize, Failure> {
+    let parsed = S::parse(input).map_err(|err| Box::new(err) as Failure)?;
+    let frames = S::frames(&parsed, part).map_err(|err| Box::new(err) as Failure)?;
+    show(frames, options).map_err(|err| Box::new(err) as Failure)
+}
+
+/// Plays `frames` in the terminal, or writes them to files if `options` asks for that.
+pub fn show(frames: impl Iterator<Item = Frame>, options: &Options) -> io::Result<usize> {
+    match &options.dump {
+        Some(directory) => dump(frames, directory),
+        None => play(frames, options),
+    }
+}
+
+// Writes each frame as plain text to `frame-00001.txt`, `frame-00002.txt` and so on
+fn dump(frames: impl Iterator<Item = Frame>, directory: &Path) -> io::Result<usize> {
+    fs::create_dir_all(directory)?;
+
+    let mut written = 0;
+    for frame in frames {
+        written += 1;
+        fs::write(directory.join(format!("frame-{:05}.txt", written)), frame.to_text())?;
+    }
+    Ok(written)
+}
+
+enum Control {
+    Step,
+    Continue,
+    Faster,
+    Slower,
+    Quit,
+}
+
+// Reads controls from stdin as they're typed. The channel disconnects once stdin is closed.
+fn controls() -> Receiver<Control> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            let control = match line.as_deref().map(str::trim) {
+                Ok("") => Control::Step,
+                Ok("c") => Control::Continue,
+                Ok("+") => Control::Faster,
+                Ok("-") => Control::Slower,
+                Ok("q") => Control::Quit,
+                Ok(_) => continue,
+                Err(_) => break,
+            };
+            if sender.send(control).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+fn play(frames: impl Iterator<Item = Frame>, options: &Options) -> io::Result<usize> {
+    let controls = controls();
+    let mut stdout = io::stdout().lock();
+    let mut speed = options.speed.clamp(MIN_SPEED, MAX_SPEED);
+    let mut paused = options.step;
+    let mut shown = 0;
+
+    // Hide the cursor and start from an empty screen
+    write!(stdout, "\x1b[?25l\x1b[2J")?;
+
+    let mut frames = frames.enumerate().peekable();
+    while let Some((i, frame)) = frames.next() {
+        let every = match paused {
+            true => 1,
+            false => (speed / MAX_REDRAWS_PER_SECOND).ceil().max(1.0) as usize,
+        };
+        // The last frame is always shown, since that's where the answer is
+        if i % every != 0 && frames.peek().is_some() {
+            continue;
+        }
+
+        let frame = match options.colour {
+            true => frame.to_ansi(),
+            false => frame.to_text(),
+        };
+        let state = match paused {
+            true => String::from("paused"),
+            false => format!("{} frames/s", speed),
+        };
+        write!(stdout, "\x1b[H{}\n[{}] {}\x1b[K\n\x1b[J", frame, state, CONTROLS)?;
+        stdout.flush()?;
+        shown += 1;
+
+        let delay = Duration::from_secs_f64(every as f64 / speed);
+        if !wait(&controls, delay, &mut paused, &mut speed) {
+            break;
+        }
+    }
+
+    write!(stdout, "\x1b[?25h")?;
+    stdout.flush()?;
+    Ok(shown)
+}
+
+// Waits until the next frame is due, or for Enter when paused, dealing with any controls
+// that come in meanwhile. Returns false when asked to quit.
+fn wait(controls: &Receiver<Control>, delay: Duration, paused: &mut bool, speed: &mut f64) -> bool {
+    let due = Instant::now() + delay;
+    loop {
+        let control = match *paused {
+            true => controls.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            false => controls.recv_timeout(due.saturating_duration_since(Instant::now())),
+        };
+
+        match control {
+            Ok(Control::Step) if *paused => return true,
+            Ok(Control::Step) => *paused = true,
+            Ok(Control::Continue) => *paused = false,
+            Ok(Control::Faster) => *speed = (*speed * 2.0).min(MAX_SPEED),
+            Ok(Control::Slower) => *speed = (*speed / 2.0).max(MIN_SPEED),
+            Ok(Control::Quit) => return false,
+            Err(RecvTimeoutError::Timeout) => return true,
+            // Nobody is left to unpause, so play the rest through
+            Err(RecvTimeoutError::Disconnected) => {
+                *paused = false;
+                thread::sleep(due.saturating_duration_since(Instant::now()));
+                return true;
+            }
+        }
+    }
+}
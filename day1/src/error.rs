@@ -0,0 +1,71 @@
+use std::fmt;
+use std::io;
+
+use common::{Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 1.
+#[derive(Debug)]
+pub enum Error {
+    InvalidCalories { span: Span, token: String },
+    /// An elf's items add up to more calories than fit in a `u64`.
+    TooManyCalories { span: Span },
+    /// The three elves carrying the most carry more calories between them than fit in a `u64`.
+    TopThreeTooLarge,
+    NoElves,
+    /// None of this elf's snacks can be moved without another elf going over the limit.
+    NoRoom { elf: usize },
+    /// This elf has a snack that's over the limit all on its own.
+    SnackTooLarge { elf: usize, calories: u64 },
+    /// A histogram in ranges of `width` calories would have more than
+    /// [`MAX_RANGES`](crate::stats::MAX_RANGES) of them.
+    TooManyRanges { width: u64, ranges: u128 },
+    /// The calorie list couldn't be read, e.g. because it isn't UTF-8.
+    Read(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidCalories { token, .. } => write!(f, "invalid calorie count `{}`", token),
+            Error::TooManyCalories { .. } => write!(f, "this elf is carrying too many calories"),
+            Error::TopThreeTooLarge => {
+                write!(f, "the top three elves carry too many calories to add up")
+            }
+            Error::NoElves => write!(f, "the input doesn't list any elves"),
+            Error::NoRoom { elf } => write!(
+                f,
+                "elf {}'s snacks can't all fit with the others without going over the limit",
+                elf
+            ),
+            Error::SnackTooLarge { elf, calories } => write!(
+                f,
+                "elf {} has a snack of {} calories, which is over the limit by itself",
+                elf, calories
+            ),
+            Error::TooManyRanges { width, ranges } => write!(
+                f,
+                "ranges of {} calories make {} of them, which is more than {}",
+                width,
+                ranges,
+                crate::stats::MAX_RANGES
+            ),
+            Error::Read(err) => write!(f, "could not read the calorie list: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidCalories { span, .. } | Error::TooManyCalories { span } => Some(*span),
+            Error::TopThreeTooLarge
+            | Error::NoElves
+            | Error::NoRoom { .. }
+            | Error::SnackTooLarge { .. }
+            | Error::TooManyRanges { .. }
+            | Error::Read(_) => None,
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! Day 1: Calorie Counting.
+//!
+//! Each elf lists the calories of the snacks they carry, one per line, with a blank line
+//! between one elf's list and the next.
+
+mod error;
+pub mod reader;
+pub mod rebalance;
+pub mod stats;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use common::{explain, Answer, Solution};
+
+pub use error::Error;
+use reader::Elf;
+
+/// Parses the input into each elf and the total calories they carry, in input order.
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<Elf>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        reader::elves(input.as_bytes())
+            .inspect(|elf| {
+                if let Ok(elf) = elf {
+                    explain!(
+                        "elf",
+                        [elf = elf.index, calories = elf.total],
+                        "{elf} carries {calories} calories"
+                    );
+                }
+            })
+            .collect()
+    }
+
+    fn part_one(elves: &Self::Input) -> Result<Answer, Error> {
+        part_one(elves).map(Answer::from)
+    }
+
+    fn part_two(elves: &Self::Input) -> Result<Answer, Error> {
+        part_two(elves).map(Answer::from)
+    }
+}
+
+/// The most calories carried by any one elf.
+pub fn part_one(elves: &[Elf]) -> Result<u64, Error> {
+    elves.iter().map(|elf| elf.total).max().ok_or(Error::NoElves)
+}
+
+/// The calories carried by the three elves carrying the most, added together.
+pub fn part_two(elves: &[Elf]) -> Result<u64, Error> {
+    if elves.is_empty() {
+        return Err(Error::NoElves);
+    }
+
+    let top_three = top_n(elves.iter().copied(), 3);
+    for (i, elf) in top_three.iter().enumerate() {
+        explain!(
+            "top three",
+            [rank = i + 1, elf = elf.index, calories = elf.total],
+            "#{rank} is elf {elf} with {calories} calories"
+        );
+    }
+    top_three
+        .iter()
+        .try_fold(0u64, |sum, elf| sum.checked_add(elf.total))
+        .ok_or(Error::TopThreeTooLarge)
+}
+
+/// The `n` elves carrying the most calories, most first. Elves carrying the same amount are
+/// ranked in the order they're listed.
+///
+/// Only the `n` best so far are kept, in a heap with the one that'd be dropped next on top,
+/// so this goes through the elves once without sorting them all.
+pub fn top_n(elves: impl IntoIterator<Item = Elf>, n: usize) -> Vec<Elf> {
+    // Ordered so that the worst elf is the greatest: the least calories, then the last listed
+    let rank = |elf: &Elf| Reverse((elf.total, Reverse(elf.index)));
+
+    // NOTE: `n` can be far more than there are elves, so the heap only grows as they're read
+    let mut heap = BinaryHeap::new();
+    for elf in elves {
+        if heap.len() < n {
+            heap.push((rank(&elf), elf.items, elf.largest));
+        } else if let Some((worst, ..)) = heap.peek() {
+            if rank(&elf) < *worst {
+                heap.pop();
+                heap.push((rank(&elf), elf.items, elf.largest));
+            }
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|(Reverse((total, Reverse(index))), items, largest)| Elf {
+            index,
+            items,
+            total,
+            largest,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{self, BufReader, Read};
+
+    use common::{fuzz, run, Part};
+    use reader::Elf;
+    use rebalance::{Target, Transfer};
+    use stats::Stats;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_finds_the_elf_carrying_the_most_calories() {
+        let elves = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part_one(&elves).unwrap(), Answer::Unsigned(24000));
+    }
+
+    #[test]
+    fn part_two_sums_the_top_three_elves() {
+        let elves = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(Day1::part_two(&elves).unwrap(), Answer::Unsigned(45000));
+    }
+
+    #[test]
+    fn top_n_ranks_the_elves_carrying_the_most() {
+        let elves = Day1::parse(EXAMPLE).unwrap();
+        let top = top_n(elves.iter().copied(), 3);
+        let top = top.iter().map(|elf| (elf.index, elf.total)).collect::<Vec<_>>();
+        assert_eq!(top, vec![(4, 24000), (3, 11000), (5, 10000)]);
+
+        assert_eq!(top_n(elves.iter().copied(), 10).len(), 5);
+        assert!(top_n(elves.iter().copied(), 0).is_empty());
+    }
+
+    #[test]
+    fn top_n_takes_any_number_of_elves() {
+        let elves = Day1::parse(EXAMPLE).unwrap();
+        assert_eq!(top_n(elves.iter().copied(), 100_000_000_000_000).len(), 5);
+        assert_eq!(top_n(elves.iter().copied(), usize::MAX).len(), 5);
+    }
+
+    #[test]
+    fn top_n_ranks_elves_carrying_as_much_in_the_order_theyre_listed() {
+        let elves = Day1::parse("100\n\n300\n\n200\n\n300\n\n100\n\n300\n").unwrap();
+        let top = top_n(elves.iter().copied(), 3).iter().map(|elf| elf.index).collect::<Vec<_>>();
+        assert_eq!(top, vec![2, 4, 6]);
+        let top = top_n(elves.iter().copied(), 5).iter().map(|elf| elf.index).collect::<Vec<_>>();
+        assert_eq!(top, vec![2, 4, 6, 3, 1]);
+    }
+
+    #[test]
+    fn stats_describe_the_whole_inventory() {
+        let stats = Stats::of(Day1::parse(EXAMPLE).unwrap()).unwrap();
+        assert_eq!((stats.elves, stats.items), (5, 10));
+        assert_eq!((stats.fewest_items, stats.most_items, stats.largest_item), (1, 3, 10000));
+        assert_eq!((stats.mean(), stats.median()), (11000.0, 10000.0));
+
+        let percentiles = [0, 25, 50, 90, 100].map(|percent| stats.percentile(percent));
+        assert_eq!(percentiles, [4000, 6000, 10000, 24000, 24000]);
+
+        let histogram = vec![(0, 1), (5000, 1), (10000, 2), (15000, 0), (20000, 1)];
+        assert_eq!(stats.histogram(5000).unwrap(), histogram);
+    }
+
+    #[test]
+    fn stats_histogram_refuses_too_many_ranges() {
+        let stats = Stats::of(Day1::parse("0\n\n18446744073709551615\n").unwrap()).unwrap();
+        assert!(matches!(
+            stats.histogram(1),
+            Err(Error::TooManyRanges { width: 1, ranges }) if ranges == 1 << 64
+        ));
+
+        let histogram = stats.histogram(u64::MAX / 2 + 1).unwrap();
+        assert_eq!(histogram, vec![(0, 1), (u64::MAX / 2 + 1, 1)]);
+        assert_eq!(stats.histogram(u64::MAX).unwrap(), vec![(0, 1), (u64::MAX, 1)]);
+    }
+
+    #[test]
+    fn stats_take_the_median_of_an_even_number_of_elves_halfway_between() {
+        let stats = Stats::of(Day1::parse("1\n\n2\n\n3\n\n10\n").unwrap()).unwrap();
+        assert_eq!(stats.median(), 2.5);
+        assert!(matches!(Stats::of(Vec::new()), Err(Error::NoElves)));
+    }
+
+    // Checks that `plan` only moved snacks around, and that solving the inventory afterwards
+    // agrees with what the plan says everyone carries
+    fn check_plan(plan: &rebalance::Plan) {
+        let mut before = reader::inventory(EXAMPLE).unwrap().concat();
+        let mut after = plan.inventory.concat();
+        before.sort();
+        after.sort();
+        assert_eq!(before, after);
+
+        let rebalanced = Day1::parse(&plan.to_input()).unwrap();
+        let most = plan.totals().into_iter().max().unwrap();
+        assert_eq!(part_one(&rebalanced).unwrap(), most);
+    }
+
+    #[test]
+    fn rebalance_keeps_every_elf_at_most_at_the_limit() {
+        let inventory = reader::inventory(EXAMPLE).unwrap();
+        let plan = rebalance::plan(&inventory, Target::AtMost(15000)).unwrap();
+        assert_eq!(plan.transfers, vec![Transfer { from: 4, to: 2, calories: 9000 }]);
+        assert_eq!(plan.totals(), vec![6000, 13000, 11000, 15000, 10000]);
+        check_plan(&plan);
+
+        let plan = rebalance::plan(&inventory, Target::AtMost(24000)).unwrap();
+        assert!(plan.transfers.is_empty());
+    }
+
+    #[test]
+    fn rebalance_narrows_the_gap_between_the_most_and_the_least() {
+        let inventory = reader::inventory(EXAMPLE).unwrap();
+        let plan = rebalance::plan(&inventory, Target::Even).unwrap();
+        let totals = plan.totals();
+        let (least, most) = (totals.iter().min().unwrap(), totals.iter().max().unwrap());
+        assert!(most - least <= 2000, "{:?} is still uneven", totals);
+        check_plan(&plan);
+    }
+
+    #[test]
+    fn rebalance_swaps_snacks_of_elves_carrying_nearly_all_that_can_be_counted() {
+        // The best step swaps the elves' largest snacks, and the second would briefly carry
+        // both
+        let inventory = [vec![u64::MAX - 1, 1], vec![u64::MAX - 3]];
+        let plan = rebalance::plan(&inventory, Target::Even).unwrap();
+        assert_eq!(plan.transfers.len(), 2);
+        assert_eq!(plan.totals(), vec![u64::MAX - 2, u64::MAX - 1]);
+    }
+
+    #[test]
+    fn rebalance_reports_limits_that_cant_be_met() {
+        let inventory = reader::inventory(EXAMPLE).unwrap();
+        assert!(matches!(
+            rebalance::plan(&inventory, Target::AtMost(9000)),
+            Err(Error::SnackTooLarge { elf: 5, calories: 10000 })
+        ));
+        assert!(matches!(
+            rebalance::plan(&inventory, Target::AtMost(10000)),
+            Err(Error::NoRoom { elf: 4 })
+        ));
+    }
+
+    #[test]
+    fn reader_yields_each_elf_with_its_snacks_added_up() {
+        let elves = reader::elves(EXAMPLE.as_bytes()).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(elves.len(), 5);
+        assert_eq!(elves[3], Elf { index: 4, items: 3, total: 24000, largest: 9000 });
+    }
+
+    #[test]
+    fn reader_stops_at_a_line_that_isnt_calories() {
+        let mut elves = reader::elves("1000\n\n2000\nlots\n3000\n".as_bytes());
+        let first = Elf { index: 1, items: 1, total: 1000, largest: 1000 };
+        assert_eq!(elves.next().unwrap().unwrap(), first);
+        match elves.next() {
+            Some(Err(Error::InvalidCalories { span, token })) => {
+                assert_eq!(span.line, 4);
+                assert_eq!(token, "lots");
+            }
+            _ => panic!("expected the second elf's list to be invalid"),
+        }
+        assert!(elves.next().is_none());
+
+        let mut elves = reader::elves(&b"1000\n\xff\n"[..]);
+        assert!(matches!(elves.next(), Some(Err(Error::Read(_)))));
+    }
+
+    // Makes up a calorie list as it's read, by reading `text` over and over
+    struct Repeat {
+        text: &'static [u8],
+        times: usize,
+        at: usize,
+    }
+
+    impl Read for Repeat {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.times == 0 {
+                return Ok(0);
+            }
+            let len = buf.len().min(self.text.len() - self.at);
+            buf[..len].copy_from_slice(&self.text[self.at..self.at + len]);
+            self.at += len;
+            if self.at == self.text.len() {
+                self.at = 0;
+                self.times -= 1;
+            }
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn reader_goes_through_lists_too_large_to_hold() {
+        // Over 2 MB of snacks, of which the reader only ever holds a line
+        let list = Repeat { text: b"3000000000\n2000000000\n\n", times: 100_000, at: 0 };
+        let (count, most) = reader::elves(BufReader::new(list))
+            .map(Result::unwrap)
+            .fold((0, 0), |(count, most), elf| (count + 1, most.max(elf.total)));
+        assert_eq!((count, most), (100_000, 5_000_000_000));
+    }
+
+    #[test]
+    fn totals_can_be_larger_than_32_bits() {
+        let elves = Day1::parse("4000000000\n1000000000\n\n3000000000\n").unwrap();
+        let totals = elves.iter().map(|elf| elf.total).collect::<Vec<_>>();
+        assert_eq!(totals, vec![5_000_000_000, 3_000_000_000]);
+        assert_eq!(part_one(&elves).unwrap(), 5_000_000_000);
+        assert_eq!(part_two(&elves).unwrap(), 8_000_000_000);
+    }
+
+    #[test]
+    fn parse_rejects_an_elf_carrying_more_than_fits() {
+        let result = Day1::parse("18446744073709551615\n1\n");
+        assert!(matches!(result, Err(Error::TooManyCalories { .. })));
+    }
+
+    #[test]
+    fn part_two_rejects_a_top_three_too_large_to_add_up() {
+        let elves = Day1::parse("18446744073709551615\n\n1\n").unwrap();
+        assert!(matches!(part_two(&elves), Err(Error::TopThreeTooLarge)));
+    }
+
+    #[test]
+    fn explains_each_elf_while_parsing() {
+        explain::enable();
+        let solved = run::solve::<Day1>(EXAMPLE, &[Part::One]).unwrap();
+        explain::disable();
+
+        let elves = solved.notes.iter().filter(|note| note.topic == "elf").collect::<Vec<_>>();
+        assert_eq!(elves.len(), 5);
+        assert_eq!(elves[3].detail, "4 carries 24000 calories");
+        assert_eq!(elves[3].fields, [("elf", "4".into()), ("calories", "24000".into())]);
+        assert!(elves.iter().all(|note| note.part.is_none()));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day1>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day1>(stress::generate, stress::brute_force, 50);
+    }
+}
@@ -1,28 +1,310 @@
-fn main() {
-    let input = include_str!("./input.txt");
-    println!("Part 1: {}", part_one(input));
-    println!("Part 2: {}", part_two(input));
+use std::env;
+use std::process::ExitCode;
+
+use common::input::Source;
+use common::{diagnostic, Diagnostic};
+use day1::reader::{self, Elf};
+use day1::rebalance::{self, Target};
+use day1::stats::Stats;
+use day1::{top_n, Day1, Error};
+
+const USAGE: &str = "\
+Usage:
+    day1 [<PATH> | -] [--format <text|json>] [--explain]
+    day1 top <N> [<PATH> | -]
+    day1 stats [--width <CALORIES>] [<PATH> | -]
+    day1 rebalance (--at-most <CALORIES> | --even) [--inventory] [<PATH> | -]
+
+'top' lists the N elves carrying the most calories. 'stats' describes the whole inventory,
+with a histogram of the elves' totals in ranges of '--width' calories, about ten ranges by
+default. Both read the input a line at a time, so the input can be far larger than would
+fit in memory.
+
+'rebalance' plans which snacks to hand between elves so that none carries more than
+'--at-most' calories, or so that they carry as close to the same as they can with '--even'.
+It prints each snack to move and what every elf carries afterwards, or with '--inventory'
+the calorie list afterwards, which can be solved like any other input.";
+
+// The longest bar in the histogram
+const BAR_WIDTH: usize = 40;
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("top") => top(&args[1..]),
+        Some("stats") => stats(&args[1..]),
+        Some("rebalance") => rebalance(&args[1..]),
+        _ => common::run::main::<Day1>(),
+    }
+}
+
+fn top(args: &[String]) -> ExitCode {
+    let (n, path) = match args {
+        [n] => (n, None),
+        [n, path] => (n, Some(path.as_str())),
+        _ => return usage_error("'top' expects how many elves to list, then the input"),
+    };
+    let Ok(n) = n.parse::<usize>() else {
+        return usage_error(&format!("'{}' isn't a number of elves", n));
+    };
+
+    let (top, count) = match read_elves(path, |elves| top_n(elves, n)) {
+        Some(read) => read,
+        None => return ExitCode::FAILURE,
+    };
+
+    println!("Top {} of {} elves by calories:", top.len(), count);
+    let width = top.len().to_string().len();
+    for (rank, elf) in top.iter().enumerate() {
+        println!(
+            "  {:>width$}. elf {} carries {} calories in {}",
+            rank + 1,
+            elf.index,
+            elf.total,
+            plural(elf.items, "snack")
+        );
+    }
+    // Added up wider than the totals, so that no number of them can overflow
+    let together = top.iter().map(|elf| u128::from(elf.total)).sum::<u128>();
+    println!("Together: {} calories", together);
+
+    ExitCode::SUCCESS
+}
+
+fn stats(args: &[String]) -> ExitCode {
+    let mut width = None;
+    let mut path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--width" => match args.next().and_then(|width| width.parse::<u64>().ok()) {
+                Some(calories) if calories > 0 => width = Some(calories),
+                _ => return usage_error("'--width' expects a number of calories above 0"),
+            },
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return usage_error(&format!("unexpected argument '{}'", arg)),
+        }
+    }
+
+    let stats = match read_elves(path.map(String::as_str), |elves| Stats::of(elves)) {
+        Some((Ok(stats), _)) => stats,
+        // Nothing else goes wrong, and no elves at all has already been reported
+        Some((Err(_), _)) | None => return ExitCode::FAILURE,
+    };
+
+    let (least, most) = (stats.percentile(0), stats.percentile(100));
+    let width = width.unwrap_or_else(|| round_width((most - least).div_ceil(10)));
+    let histogram = match stats.histogram(width) {
+        Ok(histogram) => histogram,
+        Err(err) => {
+            eprintln!("error: {}, use a larger '--width'", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!(
+        "{} carrying {}, {} to {} each and {:.1} on average",
+        plural(stats.elves, "elf"),
+        plural(stats.items, "snack"),
+        stats.fewest_items,
+        stats.most_items,
+        stats.mean_items()
+    );
+    println!("The largest snack is {} calories", stats.largest_item);
+
+    println!("\nCalories per elf:");
+    let figures = [
+        ("least", least.to_string()),
+        ("25%", stats.percentile(25).to_string()),
+        ("median", format!("{:.1}", stats.median())),
+        ("mean", format!("{:.1}", stats.mean())),
+        ("75%", stats.percentile(75).to_string()),
+        ("90%", stats.percentile(90).to_string()),
+        ("most", most.to_string()),
+    ];
+    let figure_width = figures.iter().map(|(_, figure)| figure.len()).max().unwrap_or(0);
+    for (name, figure) in &figures {
+        println!("  {:<6}  {:>figure_width$}", name, figure);
+    }
+
+    println!("\nElves by calories, in ranges of {}:", width);
+    let most_elves = histogram.iter().map(|&(_, elves)| elves).max().unwrap_or(1);
+    let range_width = (most / width * width).saturating_add(width - 1).to_string().len();
+    for (start, elves) in histogram {
+        // Every range with anyone in it gets at least some bar
+        let bar = (elves * BAR_WIDTH).div_ceil(most_elves);
+        println!(
+            "  {:>range_width$} to {:>range_width$}  {:<BAR_WIDTH$}  {}",
+            start,
+            start.saturating_add(width - 1),
+            "#".repeat(bar),
+            elves
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn rebalance(args: &[String]) -> ExitCode {
+    let mut target = None;
+    let mut inventory_only = false;
+    let mut path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let next = match arg.as_str() {
+            "--at-most" => match args.next().and_then(|limit| limit.parse::<u64>().ok()) {
+                Some(limit) => Target::AtMost(limit),
+                None => return usage_error("'--at-most' expects a number of calories"),
+            },
+            "--even" => Target::Even,
+            "--inventory" => {
+                inventory_only = true;
+                continue;
+            }
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => {
+                path = Some(arg.as_str());
+                continue;
+            }
+            _ => return usage_error(&format!("unexpected argument '{}'", arg)),
+        };
+        if target.replace(next).is_some() {
+            return usage_error("'--at-most' and '--even' can't be used together");
+        }
+    }
+    let Some(target) = target else {
+        return usage_error("'rebalance' expects either '--at-most <CALORIES>' or '--even'");
+    };
+
+    let input = match Source::from_arg(1, path).and_then(|source| source.load(1)) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let planned = reader::inventory(&input.text).and_then(|inventory| match inventory.len() {
+        0 => Err(Error::NoElves),
+        _ => Ok((rebalance::plan(&inventory, target)?, inventory)),
+    });
+    let (plan, before) = match planned {
+        Ok(planned) => planned,
+        Err(err) => {
+            eprint!("{}", diagnostic::render(&err, &input.name, &input.text));
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if inventory_only {
+        print!("{}", plan.to_input());
+        return ExitCode::SUCCESS;
+    }
+
+    match plan.transfers.len() {
+        0 => println!("Nothing has to move"),
+        count => println!("Move {}:", plural(count, "snack")),
+    }
+    for transfer in &plan.transfers {
+        println!(
+            "  elf {} gives elf {} a snack of {} calories",
+            transfer.from, transfer.to, transfer.calories
+        );
+    }
+
+    let before = before.iter().map(|snacks| snacks.iter().sum::<u64>()).collect::<Vec<_>>();
+    let after = plan.totals();
+    println!("\nWhat each elf carries afterwards:");
+    let width = after.iter().max().map_or(1, |most| most.to_string().len());
+    for (elf, (before, after)) in before.iter().zip(&after).enumerate() {
+        match before == after {
+            true => println!("  elf {}: {:>width$}", elf + 1, after),
+            false => println!("  elf {}: {:>width$} (was {})", elf + 1, after, before),
+        }
+    }
+
+    let range = |totals: &[u64]| (totals.iter().min().copied(), totals.iter().max().copied());
+    if let ((Some(least), Some(most)), (Some(least_after), Some(most_after))) =
+        (range(&before), range(&after))
+    {
+        println!(
+            "\nThe most anyone carries goes from {} to {}, and the least from {} to {}",
+            most, most_after, least, least_after
+        );
+    }
+
+    ExitCode::SUCCESS
+}
+
+// Rounds a width up to 1, 2 or 5 followed by zeroes, so the ranges start at round numbers
+fn round_width(width: u64) -> u64 {
+    let mut round = 1u64;
+    loop {
+        for step in [1, 2, 5] {
+            match round.checked_mul(step) {
+                Some(candidate) if candidate >= width => return candidate,
+                None => return width,
+                _ => {}
+            }
+        }
+        match round.checked_mul(10) {
+            Some(next) => round = next,
+            None => return width,
+        }
+    }
 }
 
-fn get_individual_elf_calorie_sum(s: &str) -> u32 {
-    s.lines()
-        .map(|x| x.parse::<u32>().unwrap())
-        .sum::<u32>()
+// Goes through the elves listed by the input at `path` with `read`, a line at a time, and
+// returns what it made of them along with how many there were. Returns `None`, having said
+// why, if the input couldn't be read or doesn't list any elves.
+fn read_elves<T>(
+    path: Option<&str>,
+    read: impl FnOnce(&mut dyn Iterator<Item = Elf>) -> T,
+) -> Option<(T, usize)> {
+    let (name, input) = match Source::from_arg(1, path).and_then(|source| source.open(1)) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return None;
+        }
+    };
+
+    let mut count = 0;
+    let mut failure = None;
+    let read = read(&mut reader::elves(input).map_while(|elf| match elf {
+        Ok(elf) => {
+            count += 1;
+            Some(elf)
+        }
+        Err(err) => {
+            failure = Some(err);
+            None
+        }
+    }));
+
+    match failure {
+        Some(err) => print_error(&name, &err),
+        None if count == 0 => print_error(&name, &Error::NoElves),
+        None => return Some((read, count)),
+    }
+    None
 }
 
-fn part_one(input: &str) -> u32 {
-    input.split("\n\n")
-        .map(get_individual_elf_calorie_sum)
-        .max()
-        .unwrap()
+// Errors can only point at the line they're on, since the input isn't kept to show it
+fn print_error(name: &str, err: &Error) {
+    match err.span() {
+        Some(span) => eprintln!("error: {}\n --> {}:{}:{}", err, name, span.line, span.column),
+        None => eprintln!("error: {}\n --> {}", err, name),
+    }
 }
 
-fn part_two(input: &str) -> u32 {
-    let mut calories_sorted = input.split("\n\n")
-        .map(get_individual_elf_calorie_sum)
-        .collect::<Vec<u32>>();
+fn plural(count: usize, noun: &str) -> String {
+    match (count, noun) {
+        (1, noun) => format!("1 {}", noun),
+        (count, "elf") => format!("{} elves", count),
+        (count, noun) => format!("{} {}s", count, noun),
+    }
+}
 
-    // Sort calories in descending order
-    calories_sorted.sort_by(|a, b| b.cmp(a));
-    calories_sorted.iter().take(3).sum()
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    ExitCode::FAILURE
 }
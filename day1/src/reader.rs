@@ -0,0 +1,136 @@
+//! Reading calorie lists a line at a time from anywhere that can be read, so that lists far
+//! too large to hold in memory can still be added up.
+
+use std::io::BufRead;
+
+use common::parse::{self, Line};
+
+use crate::Error;
+
+/// One elf's snacks, added up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    /// Where the elf is in the list, counting from 1
+    pub index: usize,
+    /// How many snacks the elf carries
+    pub items: usize,
+    pub total: u64,
+    /// The calories of the elf's largest snack
+    pub largest: u64,
+}
+
+/// The elves listed by a reader, one at a time. See [`elves`].
+pub struct Elves<R> {
+    reader: R,
+    // The line being read, kept between lines so that reading doesn't allocate each time
+    line: String,
+    line_number: usize,
+    elves: usize,
+    finished: bool,
+}
+
+/// Reads the elves from `reader` a line at a time, keeping nothing but the elf being added
+/// up. Stops after the first error, since the lines after it can't be trusted to belong to
+/// the elves they seem to.
+pub fn elves<R: BufRead>(reader: R) -> Elves<R> {
+    Elves {
+        reader,
+        line: String::new(),
+        line_number: 0,
+        elves: 0,
+        finished: false,
+    }
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let mut elf = None;
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => break,
+                Ok(_) => self.line_number += 1,
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(Error::Read(err)));
+                }
+            }
+
+            let mut text = self.line.trim_end_matches(['\n', '\r']);
+            if self.line_number == 1 {
+                text = text.strip_prefix('\u{feff}').unwrap_or(text);
+            }
+            let line = Line { number: self.line_number, text };
+
+            // Any number of blank lines separates one elf from the next
+            if line.is_blank() {
+                match elf {
+                    Some(elf) => return Some(Ok(elf)),
+                    None => continue,
+                }
+            }
+
+            let elf = elf.get_or_insert_with(|| {
+                self.elves += 1;
+                Elf { index: self.elves, items: 0, total: 0, largest: 0 }
+            });
+            let added = parse_calories(&line).and_then(|calories| {
+                let total = elf.total.checked_add(calories);
+                let total = total.ok_or(Error::TooManyCalories { span: line.span() })?;
+                Ok((calories, total))
+            });
+            match added {
+                Ok((calories, total)) => {
+                    elf.total = total;
+                    elf.items += 1;
+                    elf.largest = elf.largest.max(calories);
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        self.finished = true;
+        elf.map(Ok)
+    }
+}
+
+/// The calories of every snack each elf carries, for when the snacks themselves matter and
+/// not just how many there are, e.g. to move them around. Checks everything [`elves`] does.
+pub fn inventory(input: &str) -> Result<Vec<Vec<u64>>, Error> {
+    parse::blocks(input)
+        .iter()
+        .map(|snacks| {
+            let mut total = 0u64;
+            snacks
+                .iter()
+                .map(|snack| {
+                    let calories = parse_calories(snack)?;
+                    total = total
+                        .checked_add(calories)
+                        .ok_or(Error::TooManyCalories { span: snack.span() })?;
+                    Ok(calories)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn parse_calories(item: &Line) -> Result<u64, Error> {
+    let mut cursor = item.cursor();
+    cursor
+        .integer::<u64>()
+        .and_then(|calories| cursor.end().map(|_| calories))
+        .map_err(|_| Error::InvalidCalories {
+            span: item.span(),
+            token: item.text.to_string(),
+        })
+}
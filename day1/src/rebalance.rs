@@ -0,0 +1,169 @@
+//! Moving snacks between elves to even out how much they carry. Snacks can't be split, so
+//! this plans which whole snacks go where.
+
+use crate::Error;
+
+/// What a plan has to achieve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// No elf carries more than this many calories
+    AtMost(u64),
+    /// The elves carrying the most and the least are brought closer together a snack at a
+    /// time, handing one over or swapping two, until no single step brings them any closer
+    Even,
+}
+
+/// One snack handed from one elf to another. Elves are numbered from 1, as they're listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub from: usize,
+    pub to: usize,
+    pub calories: u64,
+}
+
+/// The snacks to move, and what everyone carries once they have been.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub transfers: Vec<Transfer>,
+    /// The calories of every snack each elf carries afterwards
+    pub inventory: Vec<Vec<u64>>,
+}
+
+impl Plan {
+    /// The total calories each elf carries afterwards.
+    pub fn totals(&self) -> Vec<u64> {
+        self.inventory.iter().map(|snacks| snacks.iter().sum()).collect()
+    }
+
+    /// The inventory afterwards written out as a calorie list, which can be solved like any
+    /// other input.
+    pub fn to_input(&self) -> String {
+        let elves = self.inventory.iter().map(|snacks| {
+            snacks.iter().map(u64::to_string).collect::<Vec<_>>().join("\n")
+        });
+        elves.collect::<Vec<_>>().join("\n\n") + "\n"
+    }
+}
+
+/// Works out which snacks to move between the elves in `inventory`, as read by
+/// [`inventory`](crate::reader::inventory), to meet `target`.
+///
+/// Plans are found greedily, a snack at a time, so they aren't always the shortest. For
+/// [`AtMost`](Target::AtMost) each elf over the limit hands snacks to whoever carries the
+/// least until it isn't, which can fail even though some other plan would succeed. No elf
+/// is ever left without any snacks, so that the calorie list still has them all.
+pub fn plan(inventory: &[Vec<u64>], target: Target) -> Result<Plan, Error> {
+    let mut planner = Planner {
+        totals: inventory.iter().map(|snacks| snacks.iter().sum()).collect(),
+        inventory: inventory.to_vec(),
+        transfers: Vec::new(),
+    };
+    match target {
+        Target::AtMost(limit) => planner.at_most(limit)?,
+        Target::Even => planner.even(),
+    }
+    Ok(Plan { transfers: planner.transfers, inventory: planner.inventory })
+}
+
+struct Planner {
+    inventory: Vec<Vec<u64>>,
+    // What each elf in `inventory` carries, kept up to date as snacks move. Parsing checks
+    // that each elf's total fits, and no elf is ever given more than another already carries.
+    totals: Vec<u64>,
+    transfers: Vec<Transfer>,
+}
+
+impl Planner {
+    fn at_most(&mut self, limit: u64) -> Result<(), Error> {
+        for (elf, snacks) in self.inventory.iter().enumerate() {
+            if let Some(&calories) = snacks.iter().find(|&&calories| calories > limit) {
+                return Err(Error::SnackTooLarge { elf: elf + 1, calories });
+            }
+        }
+
+        for from in 0..self.inventory.len() {
+            while self.totals[from] > limit {
+                // The elf carrying the least has the most room, so if a snack fits anywhere it
+                // fits there
+                let to = (0..self.inventory.len())
+                    .filter(|&to| to != from)
+                    .min_by_key(|&to| self.totals[to])
+                    .ok_or(Error::NoRoom { elf: from + 1 })?;
+                let room = limit.saturating_sub(self.totals[to]);
+                let excess = self.totals[from] - limit;
+
+                // The smallest snack that's enough on its own, or else the largest that fits
+                let fits = || self.inventory[from].iter().copied().filter(|&c| c > 0 && c <= room);
+                let calories = fits()
+                    .filter(|&calories| calories >= excess)
+                    .min()
+                    .or_else(|| fits().max())
+                    .ok_or(Error::NoRoom { elf: from + 1 })?;
+                self.transfer(from, to, calories);
+            }
+        }
+        Ok(())
+    }
+
+    fn even(&mut self) {
+        // Each step moves calories from the elf carrying the most to the elf carrying the
+        // least, fewer than the gap between them so both end up inside it. That always makes
+        // the sum of the squares of the totals smaller, so it has to stop.
+        loop {
+            // The first listed of those carrying the most, and of those carrying the least
+            let elves = 0..self.inventory.len();
+            let (Some(from), Some(to)) = (
+                elves.clone().rev().max_by_key(|&elf| self.totals[elf]),
+                elves.min_by_key(|&elf| self.totals[elf]),
+            ) else {
+                return;
+            };
+            let gap = self.totals[from] - self.totals[to];
+
+            // Either a snack handed over, or one swapped for a smaller one, whichever leaves
+            // the two of them closest to carrying the same
+            let given = self.inventory[from].iter().copied();
+            let taken = self.inventory[to].iter().copied().map(Some).chain([None]);
+            let step = given
+                .flat_map(|given| taken.clone().map(move |taken| (given, taken)))
+                .filter_map(|(given, taken)| {
+                    let moved = given.checked_sub(taken.unwrap_or(0))?;
+                    (moved > 0 && moved < gap).then_some((given, taken, moved))
+                })
+                .min_by_key(|&(.., moved)| (gap - moved).abs_diff(moved));
+            match step {
+                Some((given, taken, moved)) => {
+                    self.hand_over(from, to, given);
+                    if let Some(taken) = taken {
+                        self.hand_over(to, from, taken);
+                    }
+                    // NOTE: Only the difference is added, which leaves `to` carrying less
+                    // than `from` did. Adding all of `given` before taking `taken` back off
+                    // could go past a `u64` along the way.
+                    self.totals[from] -= moved;
+                    self.totals[to] += moved;
+                }
+                None => return,
+            }
+        }
+    }
+
+    fn transfer(&mut self, from: usize, to: usize, calories: u64) {
+        if self.hand_over(from, to, calories) {
+            self.totals[from] -= calories;
+            self.totals[to] += calories;
+        }
+    }
+
+    // Moves a snack between inventories without touching the totals. Returns whether `from`
+    // had it to give.
+    fn hand_over(&mut self, from: usize, to: usize, calories: u64) -> bool {
+        let Some(i) = self.inventory[from].iter().position(|&snack| snack == calories) else {
+            return false;
+        };
+        self.inventory[from].remove(i);
+        self.inventory[to].push(calories);
+        self.transfers.push(Transfer { from: from + 1, to: to + 1, calories });
+        true
+    }
+}
@@ -0,0 +1,105 @@
+//! Figures describing the whole inventory rather than just the elves carrying the most.
+
+use crate::reader::Elf;
+use crate::Error;
+
+/// The most ranges a [`histogram`](Stats::histogram) is split into. Any more and it's too
+/// long to read.
+pub const MAX_RANGES: u64 = 200;
+
+/// What the elves carry between them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    /// How many snacks all the elves carry
+    pub items: usize,
+    /// The fewest snacks any one elf carries
+    pub fewest_items: usize,
+    /// The most snacks any one elf carries
+    pub most_items: usize,
+    /// The calories of the largest snack anyone carries
+    pub largest_item: u64,
+    // Every elf's total, smallest first
+    totals: Vec<u64>,
+}
+
+impl Stats {
+    /// Goes through the elves once. Only their totals are kept, which the median and
+    /// percentiles need all of.
+    pub fn of(elves: impl IntoIterator<Item = Elf>) -> Result<Stats, Error> {
+        let mut stats = Stats {
+            elves: 0,
+            items: 0,
+            fewest_items: usize::MAX,
+            most_items: 0,
+            largest_item: 0,
+            totals: Vec::new(),
+        };
+        for elf in elves {
+            stats.elves += 1;
+            stats.items += elf.items;
+            stats.fewest_items = stats.fewest_items.min(elf.items);
+            stats.most_items = stats.most_items.max(elf.items);
+            stats.largest_item = stats.largest_item.max(elf.largest);
+            stats.totals.push(elf.total);
+        }
+
+        if stats.elves == 0 {
+            return Err(Error::NoElves);
+        }
+        stats.totals.sort_unstable();
+        Ok(stats)
+    }
+
+    /// The average number of snacks an elf carries.
+    pub fn mean_items(&self) -> f64 {
+        self.items as f64 / self.elves as f64
+    }
+
+    /// The average of the elves' totals.
+    pub fn mean(&self) -> f64 {
+        // Added up wider than the totals, so that no number of them can overflow
+        let sum = self.totals.iter().map(|&total| u128::from(total)).sum::<u128>();
+        sum as f64 / self.elves as f64
+    }
+
+    /// The total in the middle, or halfway between the two in the middle for an even number
+    /// of elves.
+    pub fn median(&self) -> f64 {
+        let middle = self.totals.len() / 2;
+        match self.totals.len() % 2 {
+            1 => self.totals[middle] as f64,
+            _ => (self.totals[middle - 1] as f64 + self.totals[middle] as f64) / 2.0,
+        }
+    }
+
+    /// The smallest total that at least `percent` of the elves carry no more than, so 0 gives
+    /// the smallest total and 100 the largest.
+    pub fn percentile(&self, percent: u8) -> u64 {
+        let percent = usize::from(percent.min(100));
+        let rank = (percent * self.totals.len()).div_ceil(100).max(1);
+        self.totals[rank - 1]
+    }
+
+    /// How many elves carry each range of `width` calories, starting at multiples of `width`.
+    /// Every range from the smallest total's to the largest's is there, even with nobody in
+    /// it, as the first total in the range and the number of elves. Fails if that's more than
+    /// [`MAX_RANGES`] ranges.
+    pub fn histogram(&self, width: u64) -> Result<Vec<(u64, usize)>, Error> {
+        let width = width.max(1);
+        let first = self.percentile(0) / width;
+        let last = self.percentile(100) / width;
+
+        // Counted wider, since every possible total in ranges of 1 is one more than fits
+        let ranges = u128::from(last - first) + 1;
+        if ranges > u128::from(MAX_RANGES) {
+            return Err(Error::TooManyRanges { width, ranges });
+        }
+
+        let mut counts = vec![0; ranges as usize];
+        for total in &self.totals {
+            counts[(total / width - first) as usize] += 1;
+        }
+        Ok((first..=last).map(|range| range * width).zip(counts).collect())
+    }
+}
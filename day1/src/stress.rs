@@ -0,0 +1,39 @@
+//! Random calorie lists, and a brute force solver to check answers against.
+
+use common::{Answer, Rng};
+
+/// A calorie list for `size` elves, each carrying a handful of snacks.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let elves = (0..size)
+        .map(|_| {
+            let items = (0..rng.between(1, 6)).map(|_| rng.between(1, 60000).to_string());
+            items.collect::<Vec<_>>().join("\n")
+        })
+        .collect::<Vec<_>>();
+    elves.join("\n\n") + "\n"
+}
+
+/// Adds up each elf's snacks, then picks out the largest total three times over.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let mut totals = Vec::new();
+    let mut current = None;
+    for line in input.lines() {
+        match line {
+            "" => totals.extend(current.take()),
+            item => *current.get_or_insert(0) += item.parse::<u64>().unwrap(),
+        }
+    }
+    totals.extend(current);
+
+    let most = totals.iter().copied().max();
+
+    let mut top_three = None;
+    for _ in 0..3 {
+        let Some(i) = (0..totals.len()).max_by_key(|&i| totals[i]) else {
+            break;
+        };
+        *top_three.get_or_insert(0) += totals.remove(i);
+    }
+
+    [most.map(Answer::from), top_three.map(Answer::from)]
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+
+use common::{parse, Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 10.
+#[derive(Debug)]
+pub enum Error {
+    UnknownInstruction { span: Span, token: String },
+    InvalidAmount { span: Span, token: String },
+    MissingAmount { span: Span },
+    /// The signal strengths add up to more than fits in an `i64`.
+    SignalStrengthOverflow,
+    Syntax(parse::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownInstruction { token, .. } => {
+                write!(f, "expected `noop` or `addx`, found `{}`", token)
+            }
+            Error::InvalidAmount { token, .. } => write!(f, "invalid amount `{}`", token),
+            Error::MissingAmount { .. } => write!(f, "`addx` is missing the amount to add"),
+            Error::SignalStrengthOverflow => {
+                write!(f, "the signal strengths add up to more than fits in 64 bits")
+            }
+            Error::Syntax(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::UnknownInstruction { span, .. }
+            | Error::InvalidAmount { span, .. }
+            | Error::MissingAmount { span } => Some(*span),
+            Error::Syntax(err) => err.span(),
+            Error::SignalStrengthOverflow => None,
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err: parse::Error) -> Self {
+        Error::Syntax(err)
+    }
+}
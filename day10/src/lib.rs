@@ -0,0 +1,264 @@
+//! Day 10: Cathode-Ray Tube.
+//!
+//! A program of `noop` and `addx` instructions for a CPU driving a 40 by 6 pixel screen.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+mod visualise;
+
+use common::parse::{self, Line};
+use common::{explain, Answer, Solution};
+
+pub use error::Error;
+
+/// One instruction of the program. `noop` takes a cycle, `addx` takes two and then adds
+/// its amount to the X register.
+#[derive(Debug)]
+pub enum Instruction {
+    Noop,
+    AddX(i32),
+}
+
+impl Instruction {
+    fn parse(line: &Line) -> Result<Self, Error> {
+        let mut cursor = line.cursor();
+
+        let instruction = match cursor.token() {
+            Ok("noop") => Instruction::Noop,
+            Ok("addx") => {
+                cursor.skip_whitespace();
+                let amount = cursor.integer::<i32>().map_err(|err| match err.at_end() {
+                    true => Error::MissingAmount { span: err.span },
+                    false => Error::InvalidAmount { span: err.span, token: err.found },
+                })?;
+                Instruction::AddX(amount)
+            }
+            Ok(token) => {
+                return Err(Error::UnknownInstruction {
+                    span: line.span_of(token),
+                    token: token.to_string(),
+                })
+            }
+            Err(err) => {
+                return Err(Error::UnknownInstruction { span: err.span, token: err.found })
+            }
+        };
+
+        // Each instruction takes a fixed number of cycles, so nothing can follow it
+        cursor.skip_whitespace();
+        cursor.end()?;
+
+        Ok(instruction)
+    }
+}
+
+/// Parses the input into one [`Instruction`] per line.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Instruction>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse::lines(input)
+            .filter(|line| !line.is_blank())
+            .map(|line| Instruction::parse(&line))
+            .collect()
+    }
+
+    fn part_one(program: &Self::Input) -> Result<Answer, Error> {
+        part_one(program).map(Answer::from)
+    }
+
+    fn part_two(program: &Self::Input) -> Result<Answer, Error> {
+        Ok(Answer::Screen(part_two(program)))
+    }
+}
+
+/// The sum of the signal strengths during the 20th, 60th, 100th... cycles.
+pub fn part_one(program: &[Instruction]) -> Result<i64, Error> {
+    cycles(program)
+        .filter(|cycle| cycle.number % 40 == 20)
+        .try_fold(0i64, |sum, cycle| {
+            let signal_strength = i64::try_from(cycle.number).ok()?.checked_mul(cycle.x)?;
+            explain!(
+                "cycle",
+                [cycle = cycle.number, x = cycle.x, strength = signal_strength],
+                "{cycle} has X at {x}, for a signal strength of {strength}"
+            );
+            sum.checked_add(signal_strength)
+        })
+        .ok_or(Error::SignalStrengthOverflow)
+}
+
+/// The picture drawn on the screen, with `#` for lit pixels and `.` for dark ones.
+pub fn part_two(program: &[Instruction]) -> String {
+    const LINE_LENGTH: u64 = 40;
+
+    let mut crt_output = String::new();
+    let mut current_line = String::new();
+    for cycle in cycles(program) {
+        // The pixel being drawn is lit if it's under the three pixel wide sprite
+        let pixel_position = ((cycle.number - 1) % LINE_LENGTH) as i64;
+        current_line.push(if pixel_position.abs_diff(cycle.x) <= 1 { '#' } else { '.' });
+
+        // A line only makes it onto the screen once it's finished
+        if cycle.number.is_multiple_of(LINE_LENGTH) {
+            explain!(
+                "line",
+                [line = cycle.number / LINE_LENGTH, pixels = &current_line],
+                "{line} is drawn as {pixels}"
+            );
+            current_line.push('\n');
+            crt_output.push_str(&current_line);
+            current_line.clear();
+        }
+    }
+
+    crt_output
+}
+
+/// The CPU during one cycle of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Counting from 1, as the puzzle does
+    pub number: u64,
+    /// The X register during the cycle, which is also where the middle of the sprite is
+    pub x: i64,
+    /// Which instruction is being run, counting from 0
+    pub instruction: usize,
+}
+
+/// The cycles a program runs for. See [`cycles`].
+pub struct Cycles<'a> {
+    program: &'a [Instruction],
+    next_instruction: usize,
+    // How many cycles the current instruction has left, and what it adds to X when it's done
+    remaining: u32,
+    pending: i64,
+    cycle: Cycle,
+}
+
+/// Every cycle `program` runs for.
+pub fn cycles(program: &[Instruction]) -> Cycles<'_> {
+    Cycles {
+        program,
+        next_instruction: 0,
+        remaining: 0,
+        pending: 0,
+        cycle: Cycle { number: 0, x: 1, instruction: 0 },
+    }
+}
+
+impl Iterator for Cycles<'_> {
+    type Item = Cycle;
+
+    fn next(&mut self) -> Option<Cycle> {
+        if self.remaining == 0 {
+            // The last instruction only changes X once its cycles are over.
+            // NOTE: Every amount fits in an `i32`, so X can't overflow before more than 2^32
+            // instructions have run, which is far more than a program can hold in memory.
+            self.cycle.x += std::mem::take(&mut self.pending);
+
+            (self.remaining, self.pending) = match self.program.get(self.next_instruction)? {
+                Instruction::Noop => (1, 0),
+                Instruction::AddX(amount) => (2, i64::from(*amount)),
+            };
+            self.cycle.instruction = self.next_instruction;
+            self.next_instruction += 1;
+        }
+
+        self.remaining -= 1;
+        self.cycle.number += 1;
+        Some(self.cycle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_one_example() {
+        let program = Day10::parse(EXAMPLE).unwrap();
+        assert_eq!(Day10::part_one(&program).unwrap(), Answer::Signed(13140));
+    }
+
+    #[test]
+    fn part_one_only_counts_cycles_the_program_runs_for() {
+        // The program is over before the 20th cycle starts
+        let program = Day10::parse(&"noop\n".repeat(19)).unwrap();
+        assert_eq!(part_one(&program).unwrap(), 0);
+
+        let program = Day10::parse(&"noop\n".repeat(20)).unwrap();
+        assert_eq!(part_one(&program).unwrap(), 20);
+    }
+
+    #[test]
+    fn part_two_example() {
+        let program = Day10::parse(EXAMPLE).unwrap();
+        let screen = concat!(
+            "##..##..##..##..##..##..##..##..##..##..\n",
+            "###...###...###...###...###...###...###.\n",
+            "####....####....####....####....####....\n",
+            "#####.....#####.....#####.....#####.....\n",
+            "######......######......######......####\n",
+            "#######.......#######.......#######.....\n",
+        );
+        assert_eq!(Day10::part_two(&program).unwrap(), Answer::Screen(String::from(screen)));
+    }
+
+    #[test]
+    fn cycles_run_each_instruction_for_its_cycles() {
+        let program = Day10::parse("noop\naddx 3\naddx -5\n").unwrap();
+        let cycles = cycles(&program).map(|cycle| (cycle.number, cycle.x, cycle.instruction));
+        let expected = vec![(1, 1, 0), (2, 1, 1), (3, 1, 1), (4, 4, 2), (5, 4, 2)];
+        assert_eq!(cycles.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn part_two_only_draws_finished_lines() {
+        let program = Day10::parse(&"noop\n".repeat(79)).unwrap();
+        assert_eq!(part_two(&program), format!("###{}\n", ".".repeat(37)));
+    }
+
+    #[test]
+    fn x_can_be_larger_than_32_bits() {
+        let program = "addx 2000000000\naddx 2000000000\n".to_string() + &"noop\n".repeat(16);
+        let program = Day10::parse(&program).unwrap();
+        assert_eq!(part_one(&program).unwrap(), 20 * 4_000_000_001);
+
+        let program = "addx 2000000000\n".repeat(2) + &"addx -2000000000\n".repeat(2);
+        let program = Day10::parse(&(program + &"noop\n".repeat(32))).unwrap();
+        assert_eq!(part_two(&program), format!("##{}\n", ".".repeat(38)));
+    }
+
+    #[test]
+    fn part_one_rejects_signal_strengths_too_large_to_add_up() {
+        let program = Day10::parse(&"addx 2147483647\n".repeat(50_000)).unwrap();
+        assert!(matches!(part_one(&program), Err(Error::SignalStrengthOverflow)));
+    }
+
+    #[test]
+    fn parse_rejects_anything_after_an_instruction() {
+        assert!(matches!(Day10::parse("noop 3\n"), Err(Error::Syntax(_))));
+        assert!(matches!(Day10::parse("addx 3 4\n"), Err(Error::Syntax(_))));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day10>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day10>(stress::generate, stress::brute_force, 250);
+    }
+}
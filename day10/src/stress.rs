@@ -0,0 +1,46 @@
+//! Random CPU programs, and a brute force solver to check answers against.
+
+use common::{Answer, Rng};
+
+/// A program of `size` instructions, mostly `addx` with small values as in the puzzle.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut program = String::new();
+    for _ in 0..size {
+        match rng.one_in(3) {
+            true => program.push_str("noop\n"),
+            false => program += &format!("addx {}\n", rng.between(0, 20) as i64 - 10),
+        }
+    }
+    program
+}
+
+/// Writes down the value of the X register during every cycle, then reads both answers off
+/// that list.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let mut x = 1i64;
+    let mut during = Vec::new();
+    for line in input.lines() {
+        match line.split_once(' ') {
+            Some((_, amount)) => {
+                during.extend([x, x]);
+                x += amount.parse::<i64>().unwrap();
+            }
+            None => during.push(x),
+        }
+    }
+
+    let signal_strength = (20..=during.len())
+        .step_by(40)
+        .map(|cycle| cycle as i64 * during[cycle - 1])
+        .sum::<i64>();
+
+    let mut screen = String::new();
+    for row in during.chunks_exact(40) {
+        for (column, x) in row.iter().enumerate() {
+            screen.push(if (column as i64 - x).abs() <= 1 { '#' } else { '.' });
+        }
+        screen.push('\n');
+    }
+
+    [Some(signal_strength.into()), Some(Answer::Screen(screen))]
+}
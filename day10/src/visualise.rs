@@ -0,0 +1,71 @@
+//! The CRT drawing its picture, one pixel per cycle.
+
+use common::visual::{Colour, Frame, Visualise};
+use common::Part;
+
+use crate::{cycles, Cycle, Day10, Error, Instruction};
+
+const SCREEN_WIDTH: usize = 40;
+const SCREEN_HEIGHT: usize = 6;
+
+impl Visualise for Day10 {
+    /// A frame for each cycle, showing the pixels drawn so far with the sprite underneath.
+    /// Part one also keeps count of the signal strength.
+    fn frames(
+        program: &Self::Input,
+        part: Part,
+    ) -> Result<Box<dyn Iterator<Item = Frame> + '_>, Error> {
+        let mut pixels = Vec::new();
+        let mut signal_strength = 0;
+
+        let frames = cycles(program).map(move |cycle| {
+            let column = (cycle.number as usize - 1) % SCREEN_WIDTH;
+            pixels.push((column as i64).abs_diff(cycle.x) <= 1);
+            // Added up wider than the answer, since the caption can't fail
+            if cycle.number % 40 == 20 {
+                signal_strength += i128::from(cycle.number) * i128::from(cycle.x);
+            }
+
+            let mut frame = draw(&cycle, &pixels);
+            frame.caption = match program[cycle.instruction] {
+                Instruction::Noop => format!("Cycle {}: noop, X = {}", cycle.number, cycle.x),
+                Instruction::AddX(amount) => {
+                    format!("Cycle {}: addx {}, X = {}", cycle.number, amount, cycle.x)
+                }
+            };
+            if part == Part::One {
+                frame.caption += &format!(", signal strength {}", signal_strength);
+            }
+            frame
+        });
+        Ok(Box::new(frames))
+    }
+}
+
+fn draw(cycle: &Cycle, pixels: &[bool]) -> Frame {
+    let mut frame = Frame::new(SCREEN_WIDTH, SCREEN_HEIGHT + 2);
+
+    // The screen starts over at the top once it's full
+    let drawn = (pixels.len() - 1) % (SCREEN_WIDTH * SCREEN_HEIGHT) + 1;
+    let screen = &pixels[pixels.len() - drawn..];
+    for (i, &lit) in screen.iter().enumerate() {
+        let (c, colour) = match lit {
+            true => ('#', Colour::Green),
+            false => ('.', Colour::Grey),
+        };
+        frame.draw(i % SCREEN_WIDTH, i / SCREEN_WIDTH, c, Some(colour));
+    }
+
+    // The pixel being drawn right now stands out
+    let current = drawn - 1;
+    let c = if screen[current] { '#' } else { '.' };
+    frame.draw(current % SCREEN_WIDTH, current / SCREEN_WIDTH, c, Some(Colour::White));
+
+    // The sprite is three pixels wide, centred on X
+    for x in cycle.x.saturating_sub(1)..=cycle.x.saturating_add(1) {
+        if let Ok(x) = usize::try_from(x) {
+            frame.draw(x, SCREEN_HEIGHT + 1, '=', Some(Colour::Yellow));
+        }
+    }
+    frame
+}
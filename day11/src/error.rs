@@ -0,0 +1,88 @@
+use std::fmt;
+
+use common::{parse, Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 11.
+#[derive(Debug)]
+pub enum Error {
+    UnexpectedLine { span: Span, expected: &'static str },
+    MissingLine { span: Span, expected: &'static str },
+    ExtraLine { span: Span },
+    InvalidNumber { span: Span, token: String },
+    UnknownOperator { span: Span, token: String },
+    MissingOperand { span: Span },
+    MonkeyOutOfOrder { span: Span, expected: usize },
+    UnknownMonkey { span: Span, token: String },
+    ZeroDivisor { span: Span },
+    /// A monkey's operation made a worry level too large to hold in a `u64`.
+    WorryOverflow { monkey: u8 },
+    /// The smallest number every monkey's test divides is too large for a `u64`.
+    DivisorsTooLarge,
+    /// The two busiest monkeys' inspections multiply to more than fits in a `u64`.
+    MonkeyBusinessOverflow,
+    Syntax(parse::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnexpectedLine { expected, .. } => write!(f, "expected `{}`", expected),
+            Error::MissingLine { expected, .. } => {
+                write!(f, "the monkey ends early, expected `{}` next", expected)
+            }
+            Error::ExtraLine { .. } => {
+                write!(f, "expected an empty line after the monkey's `If false` line")
+            }
+            Error::InvalidNumber { token, .. } => write!(f, "invalid number `{}`", token),
+            Error::UnknownOperator { token, .. } => {
+                write!(f, "expected an operator of `+` or `*`, found `{}`", token)
+            }
+            Error::MissingOperand { .. } => write!(f, "the operation is missing an operand"),
+            Error::MonkeyOutOfOrder { expected, .. } => {
+                write!(f, "monkeys have to be listed in order, expected monkey {}", expected)
+            }
+            Error::UnknownMonkey { token, .. } => {
+                write!(f, "there's no monkey {} to throw to", token)
+            }
+            Error::ZeroDivisor { .. } => write!(f, "the test can't be divisible by 0"),
+            Error::WorryOverflow { monkey } => {
+                write!(f, "monkey {} made a worry level too large to keep track of", monkey)
+            }
+            Error::DivisorsTooLarge => {
+                write!(f, "the monkeys' tests have no common multiple small enough to use")
+            }
+            Error::MonkeyBusinessOverflow => {
+                write!(f, "the monkeys inspected too many items to multiply the counts")
+            }
+            Error::Syntax(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::UnexpectedLine { span, .. }
+            | Error::MissingLine { span, .. }
+            | Error::ExtraLine { span }
+            | Error::InvalidNumber { span, .. }
+            | Error::UnknownOperator { span, .. }
+            | Error::MissingOperand { span }
+            | Error::MonkeyOutOfOrder { span, .. }
+            | Error::UnknownMonkey { span, .. }
+            | Error::ZeroDivisor { span } => Some(*span),
+            Error::Syntax(err) => err.span(),
+            Error::WorryOverflow { .. }
+            | Error::DivisorsTooLarge
+            | Error::MonkeyBusinessOverflow => None,
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err: parse::Error) -> Self {
+        Error::Syntax(err)
+    }
+}
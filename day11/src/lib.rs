@@ -0,0 +1,483 @@
+//! Day 11: Monkey in the Middle.
+//!
+//! Notes on each monkey: what items it holds, how it changes their worry levels and
+//! which monkey it throws them to.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use common::parse::{self, Cursor, Line};
+use common::{explain, Answer, Solution, Span};
+
+pub use error::Error;
+
+/// One monkey's notes. An item the monkey inspects has its worry level changed by
+/// `operation`, then goes to `true_condition_monkey` if it passes `test` and to
+/// `false_condition_monkey` if it doesn't.
+#[derive(Debug)]
+pub struct Monkey {
+    pub number: u8,
+    /// The worry level of each item the monkey starts with
+    pub items: Vec<u64>,
+    pub operation: Operation,
+    pub test: Divisible,
+    pub true_condition_monkey: u64,
+    pub false_condition_monkey: u64,
+}
+
+impl Monkey {
+    // Reads one monkey's notes, which have to be for the `expected`th monkey. The monkeys
+    // they throw to are recorded in `throws` so they can be checked once every monkey is
+    // known.
+    fn parse(
+        notes: &[Line],
+        expected: usize,
+        throws: &mut Vec<(Span, u64)>,
+    ) -> Result<Self, Error> {
+        let header = notes[0];
+        let mut lines = notes.iter().skip(1);
+        let mut previous_line = header;
+        let mut next_line = |label| {
+            let line = lines.next().ok_or(Error::MissingLine {
+                span: previous_line.end(),
+                expected: label,
+            })?;
+            previous_line = *line;
+            start_line(line, label)
+        };
+
+        // First line (e.g. "Monkey 1:")
+        let mut line = header.cursor();
+        line.literal("Monkey ").map_err(|_| Error::UnexpectedLine {
+            span: header.span(),
+            expected: "Monkey N:",
+        })?;
+        let (monkey_id, token) = line.consumed(parse_number::<u8>)?;
+        line.literal(":")?;
+        line.end()?;
+
+        // The solvers index each monkey's items by its number
+        if monkey_id as usize != expected {
+            return Err(Error::MonkeyOutOfOrder { span: header.span_of(token), expected });
+        }
+
+        // Second line (e.g. "Starting items: 1, 2, 3")
+        let mut line = next_line("Starting items:")?;
+        let items = match line.is_at_end() {
+            true => Vec::new(),
+            false => line.separated(",", |cursor| {
+                cursor.skip_whitespace();
+                parse_number(cursor)
+            })?,
+        };
+        line.end()?;
+
+        // Third line (e.g. "Operation: new = old * 19")
+        let mut line = next_line("Operation: new =")?;
+        let operation = Operation::parse(&mut line)?;
+        line.end()?;
+
+        // Fourth line (e.g. "Test: divisible by 3")
+        let mut line = next_line("Test: divisible by")?;
+        let test = Divisible::parse(&mut line)?;
+        line.end()?;
+
+        // Fifth and sixth lines (e.g. "If true: throw to monkey 2")
+        let mut throw = |label| {
+            let mut line = next_line(label)?;
+            let (monkey, token) = line.consumed(parse_number::<u64>)?;
+            line.end()?;
+            throws.push((line.line().span_of(token), monkey));
+            Ok::<_, Error>(monkey)
+        };
+        let true_condition_monkey = throw("If true: throw to monkey")?;
+        let false_condition_monkey = throw("If false: throw to monkey")?;
+
+        if let Some(line) = lines.next() {
+            return Err(Error::ExtraLine { span: line.span() });
+        }
+
+        Ok(Self {
+            number: monkey_id,
+            items,
+            operation,
+            test,
+            true_condition_monkey,
+            false_condition_monkey,
+        })
+    }
+}
+
+// Checks that a line of a monkey's notes starts with `label`, after any indentation, and
+// returns a cursor at whatever comes after it
+fn start_line<'a>(line: &Line<'a>, label: &'static str) -> Result<Cursor<'a>, Error> {
+    let mut cursor = line.cursor();
+    cursor.skip_whitespace();
+    cursor
+        .literal(label)
+        .map_err(|_| Error::UnexpectedLine { span: line.span(), expected: label })?;
+    cursor.skip_whitespace();
+    Ok(cursor)
+}
+
+fn parse_number<T: FromStr>(cursor: &mut Cursor) -> Result<T, Error> {
+    cursor.integer().map_err(|err| Error::InvalidNumber { span: err.span, token: err.found })
+}
+
+/// One side of an operation: either a number or the item's current worry level.
+#[derive(Debug)]
+pub enum Value {
+    Value(u64),
+    Old,
+}
+
+impl Value {
+    fn parse(cursor: &mut Cursor) -> Result<Self, Error> {
+        cursor.skip_whitespace();
+        if cursor.is_at_end() {
+            return Err(Error::MissingOperand { span: cursor.line().end() });
+        }
+        match cursor.eat("old") {
+            true => Ok(Value::Old),
+            false => parse_number(cursor).map(Value::Value),
+        }
+    }
+}
+
+/// How a monkey changes an item's worry level, e.g. `new = old * 19`.
+#[derive(Debug)]
+pub enum Operation {
+    Add(Value, Value),
+    Multiply(Value, Value),
+}
+
+#[derive(Clone, Copy)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+impl Operation {
+    fn parse(cursor: &mut Cursor) -> Result<Self, Error> {
+        let first_value = Value::parse(cursor)?;
+        let operator = cursor
+            .one_of(&[("+", Operator::Add), ("*", Operator::Multiply)])
+            .map_err(|err| match err.at_end() {
+                true => Error::MissingOperand { span: err.span },
+                false => Error::UnknownOperator { span: err.span, token: err.found },
+            })?;
+        let second_value = Value::parse(cursor)?;
+
+        match operator {
+            Operator::Add => Ok(Operation::Add(first_value, second_value)),
+            Operator::Multiply => Ok(Operation::Multiply(first_value, second_value)),
+        }
+    }
+
+    /// The new worry level, or `None` when it doesn't fit in a `u64`.
+    pub fn apply(&self, old: u64) -> Option<u64> {
+        match self {
+            Operation::Add(Value::Value(a), Value::Value(b)) => a.checked_add(*b),
+            Operation::Add(Value::Value(a), Value::Old) => a.checked_add(old),
+            Operation::Add(Value::Old, Value::Value(b)) => old.checked_add(*b),
+            Operation::Add(Value::Old, Value::Old) => old.checked_add(old),
+            Operation::Multiply(Value::Value(a), Value::Value(b)) => a.checked_mul(*b),
+            Operation::Multiply(Value::Value(a), Value::Old) => a.checked_mul(old),
+            Operation::Multiply(Value::Old, Value::Value(b)) => old.checked_mul(*b),
+            Operation::Multiply(Value::Old, Value::Old) => old.checked_mul(old),
+        }
+    }
+
+    /// The new worry level modulo `modulus`. It's worked out with 128 bits, which the sum or
+    /// product of two `u64`s always fits in, so unlike [`apply`](Self::apply) it can't
+    /// overflow.
+    pub fn apply_modulo(&self, old: u64, modulus: u64) -> u64 {
+        let value = |value: &Value| match value {
+            Value::Old => u128::from(old),
+            Value::Value(value) => u128::from(*value),
+        };
+        let new = match self {
+            Operation::Add(a, b) => value(a) + value(b),
+            Operation::Multiply(a, b) => value(a) * value(b),
+        };
+        (new % u128::from(modulus)) as u64
+    }
+}
+
+/// A monkey's test: whether the worry level is divisible by this number.
+#[derive(Debug)]
+pub struct Divisible(pub u64);
+
+impl Divisible {
+    fn parse(cursor: &mut Cursor) -> Result<Self, Error> {
+        let (value, token) = cursor.consumed(parse_number)?;
+        match value {
+            0 => Err(Error::ZeroDivisor { span: cursor.line().span_of(token) }),
+            value => Ok(Divisible(value)),
+        }
+    }
+
+    pub fn is_divisible_by(&self, value: u64) -> bool {
+        value.is_multiple_of(self.0)
+    }
+}
+
+/// Parses the input into one [`Monkey`] per set of notes, in order.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<Monkey>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let mut monkeys = Vec::new();
+        let mut throws = Vec::new();
+        for notes in parse::blocks(input) {
+            monkeys.push(Monkey::parse(&notes, monkeys.len(), &mut throws)?);
+        }
+
+        if let Some((span, monkey)) = throws
+            .into_iter()
+            .find(|(_, monkey)| *monkey as usize >= monkeys.len())
+        {
+            return Err(Error::UnknownMonkey {
+                span,
+                token: monkey.to_string(),
+            });
+        }
+
+        Ok(monkeys)
+    }
+
+    fn part_one(monkeys: &Self::Input) -> Result<Answer, Error> {
+        part_one(monkeys).map(Answer::from)
+    }
+
+    fn part_two(monkeys: &Self::Input) -> Result<Answer, Error> {
+        part_two(monkeys).map(Answer::from)
+    }
+}
+
+fn get_monkey_business(monkey_inspection_count: & HashMap<u8, u64>) -> Result<u64, Error> {
+    if explain::is_enabled() {
+        let mut counts = monkey_inspection_count.iter().collect::<Vec<_>>();
+        counts.sort();
+        for (monkey, count) in counts {
+            explain!(
+                "monkey",
+                [monkey = monkey, inspections = count],
+                "{monkey} inspected items {inspections} times"
+            );
+        }
+    }
+
+    let mut highest_count = 0;
+    let mut second_highest_count = 0;
+
+    for count in monkey_inspection_count.values() {
+        if *count > highest_count {
+            second_highest_count = highest_count;
+            highest_count = *count;
+        } else if *count > second_highest_count {
+            second_highest_count = *count;
+        }
+    }
+
+    highest_count
+        .checked_mul(second_highest_count)
+        .ok_or(Error::MonkeyBusinessOverflow)
+}
+
+fn find_gcd(a: u64, b: u64) -> u64 {
+    match b {
+        0 => a,
+        b => find_gcd(b, a % b),
+    }
+}
+
+// NOTE: Counting up until both numbers divide it takes far too long once there are several
+// large divisors, so this goes through the greatest common divisor instead.
+fn find_lcm(a: u64, b: u64) -> Option<u64> {
+    (a / find_gcd(a, b)).checked_mul(b)
+}
+
+/// The monkey business after 20 rounds, with worry levels divided by three after
+/// each inspection.
+pub fn part_one(monkeys: &[Monkey]) -> Result<u64, Error> {
+    let mut monkey_inspection_count = HashMap::new();
+    for monkey in monkeys {
+        monkey_inspection_count.insert(monkey.number, 0);
+    }
+
+    let mut monkey_items = monkeys.iter().map(|m| m.items.clone()).collect::<Vec<Vec<u64>>>();
+
+    for _ in 0..20 {
+        for Monkey { number, operation, test, true_condition_monkey, false_condition_monkey, .. } in monkeys {
+            let current_monkey_items = monkey_items[*number as usize].clone();
+            for worry_level in current_monkey_items {
+                if let Some(count) = monkey_inspection_count.get_mut(number) {
+                    *count += 1;
+                }
+
+                let worry_level = operation
+                    .apply(worry_level)
+                    .ok_or(Error::WorryOverflow { monkey: *number })?;
+                let worry_level = worry_level / 3;
+
+                if test.is_divisible_by(worry_level) {
+                    monkey_items[*true_condition_monkey as usize].push(worry_level);
+                } else {
+                    monkey_items[*false_condition_monkey as usize].push(worry_level);
+                }
+            }
+
+            // Since this monkey's thrown all their items, they no longer have anything
+            monkey_items[*number as usize].clear();
+        }
+    }
+
+    get_monkey_business(&monkey_inspection_count)
+}
+
+/// The monkey business after 10000 rounds, with worry levels left as they are.
+pub fn part_two(monkeys: &[Monkey]) -> Result<u64, Error> {
+    let lcm_among_monkey_tests = monkeys
+        .iter()
+        .try_fold(1, |acc, monkey| find_lcm(acc, monkey.test.0))
+        .ok_or(Error::DivisorsTooLarge)?;
+
+    let mut monkey_inspection_count = HashMap::new();
+    for monkey in monkeys {
+        monkey_inspection_count.insert(monkey.number, 0);
+    }
+
+    let mut monkey_items = monkeys.iter().map(|m| m.items.clone()).collect::<Vec<Vec<u64>>>();
+
+    for _ in 0..10000 {
+        for Monkey { number, operation, test, true_condition_monkey, false_condition_monkey, .. } in monkeys {
+            let current_monkey_items = monkey_items[*number as usize].clone();
+            for worry_level in current_monkey_items {
+                if let Some(count) = monkey_inspection_count.get_mut(number) {
+                    *count += 1;
+                }
+
+                // Use the LCM found from the Monkey tests to keep the worry levels manageable
+                // (i.e. not so large that they cause an arithmetic overflow). Using modular
+                // arithmetic, we can keep the important info needed for our calculations.
+                let worry_level = operation.apply_modulo(worry_level, lcm_among_monkey_tests);
+
+                if test.is_divisible_by(worry_level) {
+                    monkey_items[*true_condition_monkey as usize].push(worry_level);
+                } else {
+                    monkey_items[*false_condition_monkey as usize].push(worry_level);
+                }
+            }
+
+            // Since this monkey's thrown all their items, they no longer have anything
+            monkey_items[*number as usize].clear();
+        }
+    }
+
+    get_monkey_business(&monkey_inspection_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn operation_apply() {
+        assert_eq!(Operation::Multiply(Value::Old, Value::Value(19)).apply(79), Some(1501));
+        assert_eq!(Operation::Add(Value::Old, Value::Value(6)).apply(54), Some(60));
+        assert_eq!(Operation::Multiply(Value::Old, Value::Old).apply(79), Some(6241));
+        assert_eq!(Operation::Add(Value::Value(2), Value::Value(3)).apply(100), Some(5));
+        assert_eq!(Operation::Multiply(Value::Old, Value::Old).apply(1 << 32), None);
+    }
+
+    #[test]
+    fn parse_reports_a_monkey_that_ends_early() {
+        let input = EXAMPLE.replacen("    If false: throw to monkey 3\n", "", 1);
+        match Day11::parse(&input) {
+            Err(Error::MissingLine { span, expected }) => {
+                assert_eq!(span.line, 5);
+                assert_eq!(expected, "If false: throw to monkey");
+            }
+            _ => panic!("expected the first monkey to be missing its last line"),
+        }
+    }
+
+    #[test]
+    fn part_one_example() {
+        let monkeys = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part_one(&monkeys).unwrap(), Answer::Unsigned(10605));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let monkeys = Day11::parse(EXAMPLE).unwrap();
+        assert_eq!(Day11::part_two(&monkeys).unwrap(), Answer::Unsigned(2713310158));
+    }
+
+    #[test]
+    fn part_two_keeps_track_of_worry_levels_larger_than_32_bits() {
+        // Squaring 5000000000 doesn't fit in a u64, which is too much for part one
+        let input = "\
+Monkey 0:
+  Starting items: 5000000000
+  Operation: new = old * old
+  Test: divisible by 4294967311
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 4294967296
+  Operation: new = old + 4294967296
+  Test: divisible by 2
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+        let monkeys = Day11::parse(input).unwrap();
+        assert!(matches!(part_one(&monkeys), Err(Error::WorryOverflow { monkey: 0 })));
+        // Both items go round both monkeys every round, except that monkey 1's item only
+        // reaches monkey 0 after its turn in the first round
+        assert_eq!(part_two(&monkeys).unwrap(), 19999 * 20000);
+        assert_eq!(stress::brute_force(input)[1], Some(Answer::Unsigned(19999 * 20000)));
+    }
+
+    #[test]
+    fn part_two_rejects_tests_without_a_small_enough_common_multiple() {
+        let input = EXAMPLE.replacen("divisible by 23", "divisible by 18446744073709551557", 1);
+        let monkeys = Day11::parse(&input).unwrap();
+        assert!(matches!(part_two(&monkeys), Err(Error::DivisorsTooLarge)));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day11>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn monkey_parser_survives_fuzzing() {
+        fuzz::fuzz_parser("Monkey::parse", &[EXAMPLE], |text| -> Result<(), Error> {
+            for (expected, notes) in parse::blocks(text).iter().enumerate() {
+                Monkey::parse(notes, expected, &mut Vec::new())?;
+            }
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day11>(stress::generate, stress::brute_force, 20);
+    }
+}
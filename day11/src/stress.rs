@@ -0,0 +1,140 @@
+//! Random monkey notes, and a brute force solver to check answers against.
+
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use common::{parse, Answer, Rng};
+
+const DIVISORS: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+/// Notes for two to eight monkeys holding `size` items between them. As in the puzzle, each
+/// monkey tests for a different prime, no monkey throws to itself and at most one squares
+/// the worry level.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let count = rng.between(2, 8);
+
+    let mut divisors = DIVISORS.to_vec();
+    for i in (1..divisors.len()).rev() {
+        divisors.swap(i, rng.below(i + 1));
+    }
+
+    let mut items = vec![Vec::new(); count];
+    for _ in 0..size {
+        items[rng.below(count)].push(rng.between(50, 99).to_string());
+    }
+
+    let squaring = rng.below(count);
+    let mut notes = Vec::new();
+    for (number, items) in items.iter().enumerate() {
+        let operation = match (number == squaring, rng.one_in(2)) {
+            (true, _) => "old * old".to_string(),
+            (false, true) => format!("old + {}", rng.between(1, 8)),
+            (false, false) => format!("old * {}", rng.between(2, 19)),
+        };
+        let if_true = (number + rng.between(1, count - 1)) % count;
+        let if_false = (number + rng.between(1, count - 1)) % count;
+
+        notes.push(format!(
+            "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  \
+             Test: divisible by {}\n    If true: throw to monkey {}\n    \
+             If false: throw to monkey {}\n",
+            number,
+            items.join(", "),
+            operation,
+            divisors[number],
+            if_true,
+            if_false,
+        ));
+    }
+    notes.join("\n")
+}
+
+struct Monkey {
+    items: Vec<u128>,
+    operation: Vec<String>,
+    divisor: u128,
+    if_true: usize,
+    if_false: usize,
+}
+
+// The number at the end of the `n`th line of a monkey's notes
+fn last_number<T: FromStr>(lines: &[&str], n: usize) -> T
+where
+    T::Err: Debug,
+{
+    lines[n].rsplit(' ').next().unwrap().parse().unwrap()
+}
+
+fn parse(input: &str) -> Vec<Monkey> {
+    parse::blocks(input)
+        .into_iter()
+        .map(|notes| {
+            let lines = notes.iter().map(|line| line.text).collect::<Vec<&str>>();
+            let (_, items) = lines[1].split_once(':').unwrap();
+            let (_, operation) = lines[2].split_once("= ").unwrap();
+            Monkey {
+                items: items
+                    .split(',')
+                    .filter(|item| !item.trim().is_empty())
+                    .map(|item| item.trim().parse().unwrap())
+                    .collect(),
+                operation: operation.split(' ').map(String::from).collect(),
+                divisor: last_number(&lines, 3),
+                if_true: last_number(&lines, 4),
+                if_false: last_number(&lines, 5),
+            }
+        })
+        .collect()
+}
+
+// The monkey business after `rounds` rounds, or `None` if `relief` gives up on a worry level.
+// `relief` is applied to every worry level after it's been changed.
+fn monkey_business(
+    mut monkeys: Vec<Monkey>,
+    rounds: usize,
+    relief: impl Fn(u128) -> Option<u128>,
+) -> Option<u64> {
+    let mut inspected = vec![0u64; monkeys.len()];
+    for _ in 0..rounds {
+        for i in 0..monkeys.len() {
+            for old in std::mem::take(&mut monkeys[i].items) {
+                inspected[i] += 1;
+
+                let value = |word: &str| match word {
+                    "old" => old,
+                    number => number.parse().unwrap(),
+                };
+                let operation = &monkeys[i].operation;
+                let (a, b) = (value(&operation[0]), value(&operation[2]));
+                let new = match operation[1].as_str() {
+                    "+" => a + b,
+                    _ => a * b,
+                };
+                let new = relief(new)?;
+                let target = match new.is_multiple_of(monkeys[i].divisor) {
+                    true => monkeys[i].if_true,
+                    false => monkeys[i].if_false,
+                };
+                monkeys[target].items.push(new);
+            }
+        }
+    }
+
+    inspected.sort_unstable();
+    inspected.reverse();
+    inspected[0].checked_mul(inspected[1])
+}
+
+/// Plays every round with worry levels as 128-bit numbers. Part one gives up once a worry
+/// level no longer fits in 64 bits, like the solver does. Part two keeps them down by taking
+/// them modulo the product of every monkey's divisor, rather than the smallest common
+/// multiple the solver uses.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let product = parse(input).iter().map(|monkey| monkey.divisor).product::<u128>();
+
+    let part_one = |worry: u128| (worry <= u64::MAX.into()).then_some(worry / 3);
+    [
+        monkey_business(parse(input), 20, part_one).map(Answer::from),
+        monkey_business(parse(input), 10000, |worry| Some(worry % product)).map(Answer::from),
+    ]
+}
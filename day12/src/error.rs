@@ -0,0 +1,44 @@
+use std::fmt;
+
+use common::{Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 12.
+#[derive(Debug)]
+pub enum Error {
+    InvalidElevation { span: Span, token: String },
+    RaggedRow { span: Span, expected: usize, found: usize },
+    MissingStart,
+    MissingEnd,
+    NoPath,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidElevation { token, .. } => write!(
+                f,
+                "expected an elevation of a to z, or an S or E marker, found `{}`",
+                token
+            ),
+            Error::RaggedRow { expected, found, .. } => write!(
+                f,
+                "expected a row of {} points like the first one, found {}",
+                expected, found
+            ),
+            Error::MissingStart => write!(f, "the map has no start point `S`"),
+            Error::MissingEnd => write!(f, "the map has no end point `E`"),
+            Error::NoPath => write!(f, "no path reaches the end point"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidElevation { span, .. } | Error::RaggedRow { span, .. } => Some(*span),
+            Error::MissingStart | Error::MissingEnd | Error::NoPath => None,
+        }
+    }
+}
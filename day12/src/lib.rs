@@ -0,0 +1,263 @@
+//! Day 12: Hill Climbing Algorithm.
+//!
+//! A heightmap of letters, `a` lowest and `z` highest, with the start `S` and end `E`.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+mod visualise;
+
+use common::{explain, Answer, Part, Solution, Span};
+use grid::{Grid, ParseError, Point};
+
+pub use error::Error;
+
+const START_MARKER: u32 = 'S' as u32;
+const END_MARKER: u32 = 'E' as u32;
+const HIGHEST_ELEVATION: u32 = 'z' as u32;
+const LOWEST_ELEVATION: u32 = 'a' as u32;
+
+/// Parses the input into a grid of elevations, with the start and end still marked.
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    // The elevation at each point, along with the start and end markers
+    type Input = Grid<u32>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        build_elevation_map(input)
+    }
+
+    fn part_one(elevation_map: &Self::Input) -> Result<Answer, Error> {
+        part_one(elevation_map).map(Answer::from)
+    }
+
+    fn part_two(elevation_map: &Self::Input) -> Result<Answer, Error> {
+        part_two(elevation_map).map(Answer::from)
+    }
+}
+
+fn build_elevation_map(input: &str) -> Result<Grid<u32>, Error> {
+    let elevation = |c| match c {
+        'a'..='z' | 'S' | 'E' => Ok(c as u32),
+        _ => Err(c),
+    };
+
+    Grid::parse(input, elevation).map_err(|err| match err {
+        ParseError::Cell { point, error } => Error::InvalidElevation {
+            span: Span {
+                line: point.y + 1,
+                column: point.x + 1,
+                len: 1,
+            },
+            token: error.to_string(),
+        },
+        // Neighbours are looked up by column, so every row has to be as wide as the first
+        ParseError::RaggedRow { row, expected, found } => Error::RaggedRow {
+            span: Span {
+                line: row + 1,
+                column: 1,
+                len: found,
+            },
+            expected,
+            found,
+        },
+    })
+}
+
+// The start and end are marked on the map, but they're as high as 'a' and 'z' respectively
+fn elevation(value: u32) -> u32 {
+    match value {
+        START_MARKER => LOWEST_ELEVATION,
+        END_MARKER => HIGHEST_ELEVATION,
+        value => value,
+    }
+}
+
+fn get_start_point(map: &Grid<u32>) -> Result<Point, Error> {
+    map.position(|&elevation| elevation == START_MARKER)
+        .ok_or(Error::MissingStart)
+}
+
+fn get_end_point(map: &Grid<u32>) -> Result<Point, Error> {
+    map.position(|&elevation| elevation == END_MARKER)
+        .ok_or(Error::MissingEnd)
+}
+
+/// The fewest steps from the start to the end, climbing at most one step up at a time.
+pub fn part_one(elevation_map: &Grid<u32>) -> Result<usize, Error> {
+    fewest_steps(elevation_map, Part::One)
+}
+
+/// The fewest steps to the end from any of the lowest points.
+pub fn part_two(elevation_map: &Grid<u32>) -> Result<usize, Error> {
+    fewest_steps(elevation_map, Part::Two)
+}
+
+// How many waves the search for `part` takes to find what it's looking for
+fn fewest_steps(elevation_map: &Grid<u32>, part: Part) -> Result<usize, Error> {
+    let mut search = search(elevation_map, part)?;
+    let (steps, found) = search
+        .find_map(|wave| Some((wave.steps, wave.found?)))
+        .ok_or(Error::NoPath)?;
+
+    // Part two searches backwards, from the end to the lowest point it finds
+    match part {
+        Part::One => explain_path(get_start_point(elevation_map)?, found, steps),
+        Part::Two => explain_path(found, get_end_point(elevation_map)?, steps),
+    }
+    Ok(steps)
+}
+
+// Notes down where the shortest path found starts and ends
+fn explain_path(from: Point, to: Point, steps: usize) {
+    explain!("path", [from = from, to = to, steps = steps], "from {from} to {to} takes {steps}");
+}
+
+/// The points a search reaches in one more step than the wave before.
+#[derive(Debug, Clone)]
+pub struct Wave {
+    pub steps: usize,
+    /// Each point reached along with the point it was reached from. The starting point
+    /// is reached from itself.
+    pub reached: Vec<(Point, Point)>,
+    /// The first point reached that the search was looking for
+    pub found: Option<Point>,
+}
+
+/// A breadth first search of the map, one wave at a time. See [`search`].
+pub struct Search<'a> {
+    map: &'a Grid<u32>,
+    part: Part,
+    seen: Grid<bool>,
+    frontier: Vec<(Point, Point)>,
+    steps: usize,
+    finished: bool,
+}
+
+/// The search for the end that `part` asks for: uphill from the start for part one, or
+/// downhill from the end to any of the lowest points for part two. It finishes with the
+/// wave that finds what it's looking for, or when there's nowhere left to go.
+pub fn search(map: &Grid<u32>, part: Part) -> Result<Search<'_>, Error> {
+    let from = match part {
+        Part::One => get_start_point(map)?,
+        Part::Two => get_end_point(map)?,
+    };
+    // The end has to be there for part one to have somewhere to go
+    get_end_point(map)?;
+
+    let mut seen = Grid::filled(map.width(), map.height(), false);
+    seen[from] = true;
+
+    Ok(Search {
+        map,
+        part,
+        seen,
+        frontier: vec![(from, from)],
+        steps: 0,
+        finished: false,
+    })
+}
+
+impl Search<'_> {
+    fn can_step(&self, from: Point, to: Point) -> bool {
+        let (from, to) = (elevation(self.map[from]), elevation(self.map[to]));
+        match self.part {
+            Part::One => from + 1 >= to,
+            Part::Two => to + 1 >= from,
+        }
+    }
+
+    fn is_goal(&self, point: Point) -> bool {
+        match self.part {
+            Part::One => self.map[point] == END_MARKER,
+            Part::Two => elevation(self.map[point]) == LOWEST_ELEVATION,
+        }
+    }
+}
+
+impl Iterator for Search<'_> {
+    type Item = Wave;
+
+    fn next(&mut self) -> Option<Wave> {
+        if self.finished || self.frontier.is_empty() {
+            return None;
+        }
+
+        let reached = std::mem::take(&mut self.frontier);
+        let found = reached.iter().map(|&(point, _)| point).find(|&point| self.is_goal(point));
+        self.finished = found.is_some();
+
+        for &(current, _) in &reached {
+            for neighbour in self.map.neighbours(current) {
+                if !self.seen[neighbour] && self.can_step(current, neighbour) {
+                    self.seen[neighbour] = true;
+                    self.frontier.push((neighbour, current));
+                }
+            }
+        }
+
+        let wave = Wave { steps: self.steps, reached, found };
+        self.steps += 1;
+        Some(wave)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn finds_the_start_and_end_points() {
+        let elevation_map = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(get_start_point(&elevation_map).unwrap(), Point::new(0, 0));
+        assert_eq!(get_end_point(&elevation_map).unwrap(), Point::new(5, 2));
+    }
+
+    #[test]
+    fn part_one_example() {
+        let elevation_map = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part_one(&elevation_map).unwrap(), Answer::Unsigned(31));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let elevation_map = Day12::parse(EXAMPLE).unwrap();
+        assert_eq!(Day12::part_two(&elevation_map).unwrap(), Answer::Unsigned(29));
+    }
+
+    #[test]
+    fn markers_are_as_high_as_the_lowest_and_highest_points() {
+        // 'E' is as high as 'z' so it's one step up from 'y', and 'S' is the only 'a'
+        let elevation_map = Day12::parse("SbcdefghijklmnopqrstuvwxyE\n").unwrap();
+        assert_eq!(part_one(&elevation_map).unwrap(), 25);
+        assert_eq!(part_two(&elevation_map).unwrap(), 25);
+    }
+
+    #[test]
+    fn search_finds_the_end_in_as_many_steps_as_the_parts() {
+        let elevation_map = Day12::parse(EXAMPLE).unwrap();
+        for part in Part::BOTH {
+            let last = search(&elevation_map, part).unwrap().last().unwrap();
+            assert!(last.found.is_some());
+            assert_eq!(Day12::solve(part, &elevation_map).unwrap(), Answer::from(last.steps));
+        }
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day12>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day12>(stress::generate, stress::brute_force, 30);
+    }
+}
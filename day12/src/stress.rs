@@ -0,0 +1,106 @@
+//! Random heightmaps, and a brute force solver to check answers against.
+
+use common::{Answer, Rng};
+
+/// A heightmap `size` squares across, rising towards the end like a hill with the odd
+/// boulder or pit in the way.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.max(2);
+    let height = rng.between(1, width);
+    let distance = |(x, y): (usize, usize), to: (usize, usize)| x.abs_diff(to.0) + y.abs_diff(to.1);
+
+    // The start is at the foot of the hill, as far from the end as the map goes
+    let end = (rng.below(width), rng.below(height));
+    let corners = [(0, 0), (width - 1, 0), (0, height - 1), (width - 1, height - 1)];
+    let start = corners
+        .into_iter()
+        .filter(|&corner| corner != end)
+        .max_by_key(|&corner| distance(corner, end))
+        .unwrap();
+
+    // The hill climbs no more than one step at a time when it's at least 25 squares across,
+    // so smaller maps won't have a path
+    let across = distance(start, end).max(1);
+    let mut cells = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let elevation = match rng.one_in(6) {
+                true => rng.below(26),
+                false => 25usize.saturating_sub(distance((x, y), end) * 25 / across),
+            };
+            cells.push((b'a' + elevation as u8) as char);
+        }
+    }
+    cells[start.1 * width + start.0] = 'S';
+    cells[end.1 * width + end.0] = 'E';
+
+    cells
+        .chunks(width)
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+// How far every square is from `from`, found by shortening paths until none get any
+// shorter. `can_step` says whether one elevation can be reached from another.
+fn distances(
+    map: &[Vec<u8>],
+    from: (usize, usize),
+    can_step: impl Fn(u8, u8) -> bool,
+) -> Vec<Vec<Option<usize>>> {
+    let mut distances = vec![vec![None; map[0].len()]; map.len()];
+    distances[from.1][from.0] = Some(0);
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for y in 0..map.len() {
+            for x in 0..map[y].len() {
+                let Some(distance) = distances[y][x] else {
+                    continue;
+                };
+                let neighbours =
+                    [(x + 1, y), (x.wrapping_sub(1), y), (x, y + 1), (x, y.wrapping_sub(1))];
+                for (nx, ny) in neighbours {
+                    if ny >= map.len() || nx >= map[ny].len() || !can_step(map[y][x], map[ny][nx]) {
+                        continue;
+                    }
+                    if distances[ny][nx].is_none_or(|current| distance + 1 < current) {
+                        distances[ny][nx] = Some(distance + 1);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+    distances
+}
+
+/// Relaxes every step over and over, Bellman-Ford style, rather than searching breadth
+/// first. Part two walks down from the end and takes the closest `a`, counting the start.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let mut start = (0, 0);
+    let mut end = (0, 0);
+    let mut map = Vec::new();
+    for (y, line) in input.lines().enumerate() {
+        let mut row = line.as_bytes().to_vec();
+        for (x, square) in row.iter_mut().enumerate() {
+            match square {
+                b'S' => (start, *square) = ((x, y), b'a'),
+                b'E' => (end, *square) = ((x, y), b'z'),
+                _ => {}
+            }
+        }
+        map.push(row);
+    }
+
+    let up = distances(&map, start, |from, to| to <= from + 1);
+    let down = distances(&map, end, |from, to| from <= to + 1);
+
+    let lowest = (0..map.len())
+        .flat_map(|y| (0..map[y].len()).map(move |x| (x, y)))
+        .filter(|&(x, y)| map[y][x] == b'a')
+        .filter_map(|(x, y)| down[y][x])
+        .min();
+
+    [up[end.1][end.0].map(Answer::from), lowest.map(Answer::from)]
+}
@@ -0,0 +1,69 @@
+//! The search spreading across the heightmap, one step at a time.
+
+use common::visual::{Colour, Frame, Visualise};
+use common::Part;
+use grid::{Grid, Point};
+
+use crate::{search, Day12, Error};
+
+impl Visualise for Day12 {
+    /// A frame for each step of the search, with the points it has been to in blue and the
+    /// ones it has just reached in yellow. Once it finds what it's looking for, the last frame
+    /// traces the way back in green.
+    fn frames(
+        elevation_map: &Self::Input,
+        part: Part,
+    ) -> Result<Box<dyn Iterator<Item = Frame> + '_>, Error> {
+        let mut came_from: Grid<Option<Point>> =
+            Grid::filled(elevation_map.width(), elevation_map.height(), None);
+
+        let frames = search(elevation_map, part)?.map(move |wave| {
+            for &(point, from) in &wave.reached {
+                came_from[point] = Some(from);
+            }
+
+            let mut frame = Frame::new(elevation_map.width(), elevation_map.height());
+            for point in elevation_map.points() {
+                let colour = came_from[point].map(|_| Colour::Blue);
+                draw(&mut frame, elevation_map, point, colour);
+            }
+            for &(point, _) in &wave.reached {
+                draw(&mut frame, elevation_map, point, Some(Colour::Yellow));
+            }
+
+            frame.caption = match wave.found {
+                Some(found) => {
+                    draw_path(&mut frame, elevation_map, &came_from, found);
+                    format!("Found the way in {} steps", wave.steps)
+                }
+                None => format!("Step {}: {} more points reached", wave.steps, wave.reached.len()),
+            };
+            frame
+        });
+        Ok(Box::new(frames))
+    }
+}
+
+// Draws the point's letter as it appears in the input
+fn draw(frame: &mut Frame, elevation_map: &Grid<u32>, point: Point, colour: Option<Colour>) {
+    let letter = char::from_u32(elevation_map[point]).unwrap_or('?');
+    frame.draw(point.x, point.y, letter, colour);
+}
+
+// Follows each point back to the one it was reached from until the search's starting point,
+// which was reached from itself
+fn draw_path(
+    frame: &mut Frame,
+    elevation_map: &Grid<u32>,
+    came_from: &Grid<Option<Point>>,
+    found: Point,
+) {
+    let mut current = found;
+    loop {
+        draw(frame, elevation_map, current, Some(Colour::Green));
+        match came_from[current] {
+            Some(previous) if previous != current => current = previous,
+            _ => break,
+        }
+    }
+}
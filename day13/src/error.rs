@@ -0,0 +1,56 @@
+use std::fmt;
+
+use common::{Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 13.
+#[derive(Debug)]
+pub enum Error {
+    NotAList { span: Span },
+    UnexpectedCharacter { span: Span, token: String },
+    UnexpectedEnd { span: Span },
+    TrailingCharacters { span: Span },
+    InvalidNumber { span: Span, token: String },
+    NestedTooDeep { span: Span },
+    MissingPacket { span: Span },
+    ExtraPacket { span: Span },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAList { .. } => write!(f, "a packet has to be a list starting with `[`"),
+            Error::UnexpectedCharacter { token, .. } => {
+                write!(f, "unexpected character `{}` in the packet", token)
+            }
+            Error::UnexpectedEnd { .. } => write!(f, "the packet ends before its list is closed"),
+            Error::TrailingCharacters { .. } => {
+                write!(f, "unexpected characters after the end of the packet")
+            }
+            Error::InvalidNumber { token, .. } => write!(f, "invalid number `{}`", token),
+            Error::NestedTooDeep { .. } => {
+                write!(f, "lists can only be nested {} deep", crate::MAX_DEPTH)
+            }
+            Error::MissingPacket { .. } => write!(f, "the pair is missing its second packet"),
+            Error::ExtraPacket { .. } => {
+                write!(f, "a pair only has two packets, expected a blank line before this one")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::NotAList { span }
+            | Error::UnexpectedCharacter { span, .. }
+            | Error::UnexpectedEnd { span }
+            | Error::TrailingCharacters { span }
+            | Error::InvalidNumber { span, .. }
+            | Error::NestedTooDeep { span }
+            | Error::MissingPacket { span }
+            | Error::ExtraPacket { span } => Some(*span),
+        }
+    }
+}
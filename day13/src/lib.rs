@@ -0,0 +1,318 @@
+//! Day 13: Distress Signal.
+//!
+//! Pairs of packets, one per line with a blank line between pairs. See [`Packet`].
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use common::parse::{self, Cursor, Line};
+use common::{explain, Answer, Solution};
+
+pub use error::Error;
+
+/// How deeply lists can be nested in a packet.
+///
+/// NOTE: Parsing, comparing and dropping a packet all recurse into its lists, so a limit
+/// keeps a line of thousands of `[` from overflowing the stack. Real packets only nest a
+/// handful of lists deep.
+pub const MAX_DEPTH: usize = 256;
+
+/// A packet: a list of numbers and more lists, e.g. `[1,[2,[3]],4]`. Packets are ordered
+/// the way the puzzle compares them, with a number compared to a list as if it were a list
+/// holding just that number.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Packet {
+    Value(u32),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    /// Reads a packet written on a line of its own.
+    pub fn parse(line: &str) -> Result<Packet, Error> {
+        Self::parse_line(&Line { number: 1, text: line })
+    }
+
+    fn parse_line(line: &Line) -> Result<Packet, Error> {
+        let mut cursor = line.cursor();
+
+        // The start should always be a list
+        if !cursor.rest().starts_with('[') {
+            return Err(Error::NotAList { span: line.span() });
+        }
+        let packet = Self::parse_list(&mut cursor, 1)?;
+
+        // Nothing may follow the outermost list
+        if !cursor.is_at_end() {
+            return Err(Error::TrailingCharacters { span: line.span_of(cursor.rest()) });
+        }
+
+        Ok(packet)
+    }
+
+    // Reads a list that's `depth` lists deep, counting from 1 for the outermost one
+    fn parse_list(cursor: &mut Cursor, depth: usize) -> Result<Packet, Error> {
+        if depth > MAX_DEPTH && cursor.rest().starts_with('[') {
+            let span = cursor.line().span_of(&cursor.rest()[..1]);
+            return Err(Error::NestedTooDeep { span });
+        }
+        if !cursor.eat("[") {
+            return Err(unexpected(cursor));
+        }
+        if cursor.eat("]") {
+            return Ok(Self::List(Vec::new()));
+        }
+
+        let list = cursor.separated(",", |cursor| Self::parse_item(cursor, depth))?;
+        match cursor.eat("]") {
+            true => Ok(Self::List(list)),
+            false => Err(unexpected(cursor)),
+        }
+    }
+
+    fn parse_item(cursor: &mut Cursor, depth: usize) -> Result<Packet, Error> {
+        match cursor.rest().chars().next() {
+            Some('[') => Self::parse_list(cursor, depth + 1),
+            Some(c) if c.is_ascii_digit() => cursor
+                .integer::<u32>()
+                .map(Self::Value)
+                .map_err(|err| Error::InvalidNumber { span: err.span, token: err.found }),
+            _ => Err(unexpected(cursor)),
+        }
+    }
+
+    fn promote_to_list(n: u32) -> Vec<Packet> {
+        vec![Self::Value(n)]
+    }
+}
+
+// Whatever is next on the line when it isn't what a packet needs
+fn unexpected(cursor: &Cursor) -> Error {
+    let line = cursor.line();
+    match cursor.rest().chars().next() {
+        Some(c) => {
+            let token = &cursor.rest()[..c.len_utf8()];
+            Error::UnexpectedCharacter { span: line.span_of(token), token: token.to_string() }
+        }
+        None => Error::UnexpectedEnd { span: line.end() },
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match self {
+            Self::Value(n) => match other {
+                Self::Value(other_n) => n.cmp(other_n),
+                // If the other is a List, then we need to promote self to a List before comparing
+                Self::List(other_list) => Self::promote_to_list(*n).cmp(other_list),
+            },
+            Self::List(list) => match other {
+                // If the other is a Value, then we need to promote other to a List before comparing
+                Self::Value(other_n) => list.cmp(&Self::promote_to_list(*other_n)),
+                Self::List(other_list) => list.cmp(other_list),
+            },
+        }
+    }
+}
+
+/// Parses the input into each pair of packets.
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<(Packet, Packet)>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        // Pairs are separated by blank lines
+        parse::blocks(input)
+            .iter()
+            .map(|pair| match pair[..] {
+                [first] => Err(Error::MissingPacket { span: first.end() }),
+                [first, second] => Ok((Packet::parse_line(&first)?, Packet::parse_line(&second)?)),
+                [_, _, extra, ..] => Err(Error::ExtraPacket { span: extra.span() }),
+                [] => unreachable!("blocks are never empty"),
+            })
+            .collect()
+    }
+
+    fn part_one(pairs: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_one(pairs).into())
+    }
+
+    fn part_two(pairs: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_two(pairs).into())
+    }
+}
+
+/// The sum of the (1-based) indices of the pairs that are in the right order.
+///
+/// NOTE: This and the decoder key grow with the square of the number of packets, so they're
+/// worked out as `u64` rather than a `usize` that may only be 32 bits.
+pub fn part_one(pairs: &[(Packet, Packet)]) -> u64 {
+    pairs
+        .iter()
+        .map(|(left, right)| left.partial_cmp(right))
+        .enumerate()
+        .map(|(i, order)| {
+            let verdict = match order {
+                Some(std::cmp::Ordering::Less) => "in the right order",
+                Some(std::cmp::Ordering::Equal) => "two equal packets",
+                _ => "in the wrong order",
+            };
+            explain!("pair", [pair = i + 1, verdict = verdict], "{pair} is {verdict}");
+
+            match order {
+                // Add 1 here since the input index starts at 1
+                Some(std::cmp::Ordering::Less) => i as u64 + 1,
+                _ => 0,
+            }
+        })
+        .sum()
+}
+
+/// The decoder key: the product of where the divider packets `[[2]]` and `[[6]]` end
+/// up once every packet is sorted.
+pub fn part_two(pairs: &[(Packet, Packet)]) -> u64 {
+    let first_divider_packet = Packet::List(vec![Packet::List(vec![Packet::Value(2)])]);
+    let second_divider_packet = Packet::List(vec![Packet::List(vec![Packet::Value(6)])]);
+
+    // Add the divider packets to the received packets
+    let mut packets = pairs
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .chain([&first_divider_packet, &second_divider_packet])
+        .collect::<Vec<_>>();
+
+    // Sort the packets and transform it back into an iterator
+    packets.sort();
+    let mut sorted_packets = packets.iter();
+
+    // Find the index of the divider packets and add 1 since the packets are indexed starting at 1
+    let first_divider_packet_index = sorted_packets
+        .position(|packet| **packet == first_divider_packet)
+        .unwrap()
+        + 1;
+    let second_divider_packet_index = sorted_packets
+        .position(|packet| **packet == second_divider_packet)
+        .unwrap()
+        + 1;
+
+    // Since the call to 'position' modifies the underlying Iterator by returning the remaining items
+    // from where it found the element, the second call to 'position' on the Iterator will start from
+    // where the the previous item was found. So we need to add both indexes to get the second index.
+    let (first, second) = (first_divider_packet_index as u64, second_divider_packet_index as u64);
+    first * (first + second)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::*;
+    use common::{fuzz, run, Part};
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn compare_two_packets_correctly() {
+        let packet_a = Packet::parse("[[1],[2,3,4]]").unwrap();
+        let packet_b = Packet::parse("[[1],4]").unwrap();
+        assert_eq!(
+            packet_a.partial_cmp(&packet_b),
+            Some(std::cmp::Ordering::Less)
+        )
+    }
+
+    #[test]
+    fn packet_ordering() {
+        let compare = |left, right| {
+            let left = Packet::parse(left).unwrap();
+            left.cmp(&Packet::parse(right).unwrap())
+        };
+
+        assert_eq!(compare("[1,1,3,1,1]", "[1,1,5,1,1]"), Ordering::Less);
+        assert_eq!(compare("[9]", "[[8,7,6]]"), Ordering::Greater);
+        assert_eq!(compare("[[4,4],4,4]", "[[4,4],4,4,4]"), Ordering::Less);
+        assert_eq!(compare("[7,7,7,7]", "[7,7,7]"), Ordering::Greater);
+        assert_eq!(compare("[]", "[3]"), Ordering::Less);
+        assert_eq!(compare("[[[]]]", "[[]]"), Ordering::Greater);
+        assert_eq!(compare("[[1],[2]]", "[[1],[2]]"), Ordering::Equal);
+        assert_eq!(
+            compare("[1,[2,[3,[4,[5,6,7]]]],8,9]", "[1,[2,[3,[4,[5,6,0]]]],8,9]"),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn packets_can_only_nest_so_deep() {
+        let deepest = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        let packet = Packet::parse(&deepest).unwrap();
+        assert_eq!(packet.cmp(&Packet::parse(&deepest).unwrap()), Ordering::Equal);
+
+        match Packet::parse(&"[".repeat(20_000)) {
+            Err(Error::NestedTooDeep { span }) => {
+                assert_eq!((span.line, span.column, span.len), (1, MAX_DEPTH + 1, 1));
+            }
+            _ => panic!("expected the packet to be nested too deep"),
+        }
+    }
+
+    #[test]
+    fn part_one_example() {
+        let pairs = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part_one(&pairs).unwrap(), Answer::Unsigned(13));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let pairs = Day13::parse(EXAMPLE).unwrap();
+        assert_eq!(Day13::part_two(&pairs).unwrap(), Answer::Unsigned(140));
+    }
+
+    #[test]
+    fn explains_the_order_of_each_pair_only_when_asked() {
+        let solved = run::solve::<Day13>(EXAMPLE, &[Part::One]).unwrap();
+        assert!(solved.notes.is_empty());
+
+        explain::enable();
+        let solved = run::solve::<Day13>(EXAMPLE, &[Part::One]).unwrap();
+        explain::disable();
+
+        let wrong = solved.notes.iter();
+        let wrong = wrong.filter_map(|note| note.detail.strip_suffix(" is in the wrong order"));
+        assert_eq!(wrong.collect::<Vec<_>>(), ["3", "5", "7", "8"]);
+        assert!(solved.notes.iter().all(|note| note.part == Some(Part::One)));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day13>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn list_parser_survives_fuzzing() {
+        // Nested as deep as allowed, so that repeating part of it goes too deep
+        let deepest = "[".repeat(MAX_DEPTH) + &"]".repeat(MAX_DEPTH);
+        let mut packets = EXAMPLE.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>();
+        packets.push(&deepest);
+
+        fuzz::fuzz_parser("Packet::parse_list", &packets, |text| {
+            let mut lines = parse::lines(text);
+            lines.try_for_each(|line| Packet::parse_list(&mut line.cursor(), 1).map(drop))
+        });
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day13>(stress::generate, stress::brute_force, 50);
+    }
+}
@@ -0,0 +1,110 @@
+//! Random packet pairs, and a brute force solver to check answers against.
+
+use std::cmp::Ordering;
+
+use common::{Answer, Rng};
+
+/// `size` pairs of packets nested up to four lists deep. Packets that compare equal to a
+/// divider packet are left out, since the puzzle doesn't say where those would be sorted.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let dividers = [parse("[[2]]"), parse("[[6]]")];
+
+    let mut pairs = Vec::new();
+    while pairs.len() < size * 2 {
+        let packet = packet(rng, 0);
+        let item = parse(&packet);
+        if dividers.iter().all(|divider| compare(&item, divider) != Ordering::Equal) {
+            pairs.push(packet);
+        }
+    }
+
+    let pairs = pairs.chunks(2).map(|pair| format!("{}\n{}\n", pair[0], pair[1]));
+    pairs.collect::<Vec<_>>().join("\n")
+}
+
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let items = (0..rng.below(5))
+        .map(|_| match depth < 3 && rng.one_in(3) {
+            true => packet(rng, depth + 1),
+            false => rng.below(11).to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
+
+enum Item {
+    Number(u32),
+    List(Vec<Item>),
+}
+
+fn parse(packet: &str) -> Item {
+    fn item(bytes: &[u8], at: &mut usize) -> Item {
+        if bytes[*at] != b'[' {
+            let start = *at;
+            while bytes[*at].is_ascii_digit() {
+                *at += 1;
+            }
+            let number = std::str::from_utf8(&bytes[start..*at]).unwrap();
+            return Item::Number(number.parse().unwrap());
+        }
+
+        *at += 1;
+        let mut list = Vec::new();
+        while bytes[*at] != b']' {
+            list.push(item(bytes, at));
+            if bytes[*at] == b',' {
+                *at += 1;
+            }
+        }
+        *at += 1;
+        Item::List(list)
+    }
+
+    item(packet.as_bytes(), &mut 0)
+}
+
+// The rules from the puzzle, one by one
+fn compare(left: &Item, right: &Item) -> Ordering {
+    match (left, right) {
+        (Item::Number(left), Item::Number(right)) => left.cmp(right),
+        (Item::List(left), Item::List(right)) => {
+            for i in 0.. {
+                match (left.get(i), right.get(i)) {
+                    (None, None) => return Ordering::Equal,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(left), Some(right)) => match compare(left, right) {
+                        Ordering::Equal => continue,
+                        order => return order,
+                    },
+                }
+            }
+            unreachable!()
+        }
+        (Item::Number(left), right) => compare(&Item::List(vec![Item::Number(*left)]), right),
+        (left, Item::Number(right)) => compare(left, &Item::List(vec![Item::Number(*right)])),
+    }
+}
+
+/// Compares packets with the rules spelled out one at a time. Part two counts how many
+/// packets come before each divider instead of sorting them.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let packets = input.lines().filter(|line| !line.is_empty()).map(parse).collect::<Vec<_>>();
+
+    let in_order = packets
+        .chunks(2)
+        .enumerate()
+        .filter(|(_, pair)| compare(&pair[0], &pair[1]) == Ordering::Less)
+        .map(|(i, _)| i + 1)
+        .sum::<usize>();
+
+    let before = |divider: &str| {
+        let divider = parse(divider);
+        let smaller = packets.iter().filter(|packet| compare(packet, &divider) == Ordering::Less);
+        smaller.count()
+    };
+    // The first divider comes before the second, pushing it one further along
+    let decoder_key = (before("[[2]]") + 1) * (before("[[6]]") + 2);
+
+    [Some(in_order.into()), Some(decoder_key.into())]
+}
@@ -0,0 +1,49 @@
+use std::fmt;
+
+use common::{parse, Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 14.
+#[derive(Debug)]
+pub enum Error {
+    InvalidCoordinate { span: Span, token: String },
+    CoordinateTooLarge { span: Span, max: usize },
+    MissingComma { span: Span },
+    DiagonalPath { span: Span },
+    Syntax(parse::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidCoordinate { token, .. } => write!(f, "invalid coordinate `{}`", token),
+            Error::CoordinateTooLarge { max, .. } => {
+                write!(f, "coordinates can't be larger than {}", max)
+            }
+            Error::MissingComma { .. } => write!(f, "expected a point written as `x,y`"),
+            Error::DiagonalPath { .. } => {
+                write!(f, "rock paths only run horizontally or vertically")
+            }
+            Error::Syntax(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidCoordinate { span, .. }
+            | Error::CoordinateTooLarge { span, .. }
+            | Error::MissingComma { span }
+            | Error::DiagonalPath { span } => Some(*span),
+            Error::Syntax(err) => err.span(),
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err: parse::Error) -> Self {
+        Error::Syntax(err)
+    }
+}
@@ -0,0 +1,305 @@
+//! Day 14: Regolith Reservoir.
+//!
+//! Paths of rock, e.g. `498,4 -> 498,6 -> 496,6`, in a cave that sand falls into from
+//! `500,0`.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+mod visualise;
+
+use common::parse::{self, Cursor};
+use common::{explain, Answer, Part, Solution, Span};
+use grid::{Direction, Point, SparseGrid};
+
+pub use error::Error;
+
+const SAND_FALL_START: Point = Point::new(500, 0);
+
+// NOTE: Real inputs stay well below this. Every point of a path gets stored and part two
+// fills a triangle as tall as the lowest rock, so much larger caves would never finish.
+const MAX_COORDINATE: usize = 10_000;
+
+// Sand spreads at most one to the side for each row it falls, and part two's floor is two
+// below the lowest rock. Keeping rocks this high up means sand never has to go left of
+// `x = 0`, which coordinates can't hold.
+const MAX_DEPTH: usize = SAND_FALL_START.x - 2;
+
+// The directions a grain of sand tries to fall in, in order
+const FALL_DIRECTIONS: [Direction; 3] =
+    [Direction::Down, Direction::DownLeft, Direction::DownRight];
+
+/// What fills a point of the cave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Rock,
+    Sand,
+}
+
+/// Parses the input into every point of the cave taken up by rock.
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    // Every space occupied by rock
+    type Input = SparseGrid<Tile>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        get_coordinates(input)
+    }
+
+    fn part_one(rocks: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_one(rocks).into())
+    }
+
+    fn part_two(rocks: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_two(rocks).into())
+    }
+}
+
+fn get_coordinates(input: &str) -> Result<SparseGrid<Tile>, Error> {
+    let mut rocks = SparseGrid::new();
+
+    for line in parse::lines(input).filter(|line| !line.is_blank()) {
+        // Parse each coordinate on this line into tuples of (x, y) points, keeping the
+        // text of each point around to point at it if its path turns out to be diagonal
+        let mut cursor = line.cursor();
+        let points = cursor.separated(" -> ", |cursor| cursor.consumed(parse_point))?;
+        cursor.end()?;
+
+        // Find the coordinates that make up the line between each point
+        for pair in points.windows(2) {
+            if let [(a, a_text), (b, b_text)] = pair {
+                if a.x != b.x && a.y != b.y {
+                    let start = line.span_of(a_text);
+                    let end = line.span_of(b_text);
+                    return Err(Error::DiagonalPath {
+                        span: Span { len: end.column + end.len - start.column, ..start },
+                    });
+                }
+
+                if a.x != b.x {
+                    // They differ on the x-axis which means the line runs along the x-axis.
+                    // Find the all the coordinates between them that make up the line.
+                    let x_max = std::cmp::max(a.x, b.x);
+                    let x_min = std::cmp::min(a.x, b.x);
+
+                    for x in x_min..x_max {
+                        rocks.insert(Point::new(x, a.y), Tile::Rock);
+                    }
+                } else {
+                    // Same thing, but for the y-axis
+                    let y_max = std::cmp::max(a.y, b.y);
+                    let y_min = std::cmp::min(a.y, b.y);
+
+                    for y in y_min..y_max {
+                        rocks.insert(Point::new(a.x, y), Tile::Rock);
+                    }
+                }
+
+                rocks.insert(*a, Tile::Rock);
+                rocks.insert(*b, Tile::Rock);
+            }
+        }
+    }
+
+    Ok(rocks)
+}
+
+fn parse_point(cursor: &mut Cursor) -> Result<Point, Error> {
+    let x = parse_coordinate(cursor, MAX_COORDINATE)?;
+    cursor.literal(",").map_err(|err| Error::MissingComma { span: err.span })?;
+    let y = parse_coordinate(cursor, MAX_DEPTH)?;
+    Ok(Point::new(x, y))
+}
+
+fn parse_coordinate(cursor: &mut Cursor, max: usize) -> Result<usize, Error> {
+    let (coordinate, token) = cursor
+        .consumed(Cursor::integer::<usize>)
+        .map_err(|err| Error::InvalidCoordinate { span: err.span, token: err.found })?;
+    match coordinate <= max {
+        true => Ok(coordinate),
+        false => Err(Error::CoordinateTooLarge { span: cursor.line().span_of(token), max }),
+    }
+}
+
+// Where a grain of sand at `sand` falls to next, if it can still move
+fn next_fall(cave: &SparseGrid<Tile>, sand: Point) -> Option<Point> {
+    FALL_DIRECTIONS
+        .into_iter()
+        .filter_map(|direction| sand.step(direction))
+        .find(|&point| !cave.contains(point))
+}
+
+/// How much sand comes to rest before it starts falling into the abyss below the rocks.
+pub fn part_one(rocks: &SparseGrid<Tile>) -> usize {
+    count_grains(rocks, Part::One)
+}
+
+/// How much sand comes to rest on the floor below the rocks before its source is blocked.
+pub fn part_two(rocks: &SparseGrid<Tile>) -> usize {
+    count_grains(rocks, Part::Two)
+}
+
+fn count_grains(rocks: &SparseGrid<Tile>, part: Part) -> usize {
+    pour(rocks, part)
+        .enumerate()
+        .inspect(|&(i, rest)| explain_grain(i + 1, rest))
+        .count()
+}
+
+// Notes down where a grain of sand came to rest
+fn explain_grain(grain: usize, rest: Point) {
+    explain!("grain", [grain = grain, x = rest.x, y = rest.y], "{grain} comes to rest at {x},{y}");
+}
+
+/// Sand falling into the cave one grain at a time, yielding where each grain comes to rest.
+/// See [`pour`].
+pub struct Pour {
+    cave: SparseGrid<Tile>,
+    part: Part,
+    // The abyss for part one, or the lowest row sand can rest on for part two
+    bottom: usize,
+    // Where the last grain fell through on its way to rest, from the source down. Only its
+    // resting place has changed, so the next grain falls the same way until just above it.
+    path: Vec<Point>,
+    finished: bool,
+}
+
+/// The sand that `part` pours: until it falls into the abyss below the rocks for part one,
+/// or onto a floor two below them until the source is blocked for part two.
+pub fn pour(rocks: &SparseGrid<Tile>, part: Part) -> Pour {
+    let lowest = rocks.bounds().map_or(0, |(_, max)| max.y);
+    Pour {
+        cave: rocks.clone(),
+        part,
+        bottom: match part {
+            Part::One => lowest,
+            Part::Two => lowest + 1,
+        },
+        path: vec![SAND_FALL_START],
+        finished: false,
+    }
+}
+
+impl Pour {
+    /// The rocks and all the sand at rest so far.
+    pub fn cave(&self) -> &SparseGrid<Tile> {
+        &self.cave
+    }
+
+    // Leaves the grain where it is, at the end of the path
+    fn rest(&mut self, sand: Point) -> Point {
+        self.path.pop();
+        self.cave.insert(sand, Tile::Sand);
+        sand
+    }
+}
+
+impl Iterator for Pour {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        if self.finished {
+            return None;
+        }
+
+        // Once the source itself is full, there's nowhere left for the path to start
+        while let Some(&sand) = self.path.last() {
+            if sand.y >= self.bottom {
+                match self.part {
+                    Part::One => break,
+                    Part::Two => return Some(self.rest(sand)),
+                }
+            }
+            match next_fall(&self.cave, sand) {
+                Some(next) => self.path.push(next),
+                None => return Some(self.rest(sand)),
+            }
+        }
+
+        self.finished = true;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn get_coordinates_fills_in_each_path() {
+        let rocks = get_coordinates("498,4 -> 498,6 -> 496,6").unwrap();
+
+        let mut points = rocks.iter().map(|(point, _)| (point.x, point.y)).collect::<Vec<_>>();
+        points.sort();
+        assert_eq!(points, vec![(496, 6), (497, 6), (498, 4), (498, 5), (498, 6)]);
+    }
+
+    #[test]
+    fn get_coordinates_rejects_huge_caves() {
+        assert!(matches!(
+            get_coordinates("500,1 -> 500,20000"),
+            Err(Error::CoordinateTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn get_coordinates_rejects_rocks_too_deep_for_sand_to_stay_right_of_zero() {
+        assert!(matches!(
+            get_coordinates("0,499 -> 1,499"),
+            Err(Error::CoordinateTooLarge { max: 498, .. })
+        ));
+        assert!(get_coordinates("0,498 -> 1,498").is_ok());
+    }
+
+    #[test]
+    fn part_one_stops_when_the_sand_reaches_its_source() {
+        let rocks = get_coordinates("498,1 -> 498,3 -> 502,3 -> 502,1").unwrap();
+        assert_eq!(part_one(&rocks), 7);
+    }
+
+    #[test]
+    fn part_one_example() {
+        let rocks = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part_one(&rocks).unwrap(), Answer::Unsigned(24));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let rocks = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(Day14::part_two(&rocks).unwrap(), Answer::Unsigned(93));
+    }
+
+    #[test]
+    fn pour_drops_as_much_sand_as_the_parts() {
+        let rocks = Day14::parse(EXAMPLE).unwrap();
+        assert_eq!(pour(&rocks, Part::One).count(), 24);
+        assert_eq!(pour(&rocks, Part::Two).count(), 93);
+
+        let rocks = get_coordinates("498,1 -> 498,3 -> 502,3 -> 502,1").unwrap();
+        assert_eq!(pour(&rocks, Part::One).last(), Some(SAND_FALL_START));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day14>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn path_parser_survives_fuzzing() {
+        let paths = EXAMPLE.lines().collect::<Vec<_>>();
+        fuzz::fuzz_parser("get_coordinates", &paths, |text| get_coordinates(text).map(drop));
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day14>(stress::generate, stress::brute_force, 40);
+    }
+}
@@ -0,0 +1,85 @@
+//! Random rock paths, and a brute force solver to check answers against.
+
+use common::{Answer, Rng};
+
+/// `size` paths of rock in a cave that gets deeper and wider with `size`, centred under
+/// where the sand falls from.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let depth = 10 + size;
+    let (left, right) = (500 - depth / 2, 500 + depth / 2);
+
+    let mut paths = String::new();
+    for _ in 0..size {
+        let (mut x, mut y) = (rng.between(left, right), rng.between(1, depth));
+        let mut points = vec![format!("{},{}", x, y)];
+        for i in 0..rng.between(1, 4) {
+            // Paths turn a corner at every point
+            match i % 2 == 0 {
+                true => x = rng.between(x.saturating_sub(8).max(left), (x + 8).min(right)),
+                false => y = rng.between(y.saturating_sub(8).max(1), (y + 8).min(depth)),
+            }
+            points.push(format!("{},{}", x, y));
+        }
+        paths += &points.join(" -> ");
+        paths.push('\n');
+    }
+    paths
+}
+
+// Drops grains of sand one at a time into `cave` until one falls below `bottom` or the
+// source is blocked, and returns how many came to rest
+fn pour(cave: &mut [Vec<bool>], bottom: usize) -> usize {
+    let mut resting = 0;
+    while !cave[0][500] {
+        let (mut x, mut y) = (500, 0);
+        loop {
+            if y >= bottom {
+                return resting;
+            }
+            match [x, x - 1, x + 1].into_iter().find(|&x| !cave[y + 1][x]) {
+                Some(next) => (x, y) = (next, y + 1),
+                None => break,
+            }
+        }
+        cave[y][x] = true;
+        resting += 1;
+    }
+    resting
+}
+
+/// Draws the rock into a plain two dimensional array and lets the sand fall through it
+/// one square at a time.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let paths = input
+        .lines()
+        .map(|line| {
+            let points = line.split(" -> ").map(|point| {
+                let (x, y) = point.split_once(',').unwrap();
+                (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap())
+            });
+            points.collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let lowest = paths.iter().flatten().map(|&(_, y)| y).max().unwrap_or(0);
+    let floor = lowest + 2;
+
+    // Wide enough for the heap of sand on the floor to spread out in both directions
+    let mut cave = vec![vec![false; 500 + floor + 2]; floor + 1];
+    for path in &paths {
+        for pair in path.windows(2) {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            for row in &mut cave[y1.min(y2)..=y1.max(y2)] {
+                row[x1.min(x2)..=x1.max(x2)].fill(true);
+            }
+        }
+    }
+
+    let into_the_abyss = pour(&mut cave.clone(), lowest);
+
+    let last = cave.len() - 1;
+    cave[last].fill(true);
+    let onto_the_floor = pour(&mut cave, floor);
+
+    [Some(into_the_abyss.into()), Some(onto_the_floor.into())]
+}
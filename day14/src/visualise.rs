@@ -0,0 +1,82 @@
+//! The cave filling up with sand, one grain at a time.
+
+use std::iter;
+
+use common::visual::{Colour, Frame, Visualise};
+use common::Part;
+use grid::{Point, SparseGrid};
+
+use crate::{pour, Day14, Error, Tile, SAND_FALL_START};
+
+impl Visualise for Day14 {
+    /// A frame for each grain of sand that comes to rest, with the newest one in white. The
+    /// frames cover everywhere sand could end up, so the picture stays still as it fills.
+    fn frames(
+        rocks: &Self::Input,
+        part: Part,
+    ) -> Result<Box<dyn Iterator<Item = Frame> + '_>, Error> {
+        let (min, max) = rocks.bounds().unwrap_or((SAND_FALL_START, SAND_FALL_START));
+
+        // Sand spreads at most one to the side for each row it falls, and for part two it
+        // can spread out along the floor past the ends of the rocks
+        let (left, right, floor) = match part {
+            Part::One => (min.x.saturating_sub(1), max.x + 1, None),
+            Part::Two => {
+                let floor = max.y + 2;
+                let left = min.x.min(SAND_FALL_START.x.saturating_sub(floor));
+                (left, max.x.max(SAND_FALL_START.x + floor), Some(floor))
+            }
+        };
+        let area = Area {
+            left,
+            width: right - left + 1,
+            height: floor.unwrap_or(max.y) + 1,
+            floor,
+        };
+
+        let mut pour = pour(rocks, part);
+        let mut at_rest = 0;
+        let frames = iter::from_fn(move || {
+            let sand = pour.next()?;
+            at_rest += 1;
+            Some(draw(&area, pour.cave(), sand, at_rest))
+        });
+        Ok(Box::new(frames))
+    }
+}
+
+// The part of the cave that's drawn
+struct Area {
+    left: usize,
+    width: usize,
+    height: usize,
+    floor: Option<usize>,
+}
+
+fn draw(area: &Area, cave: &SparseGrid<Tile>, newest: Point, at_rest: usize) -> Frame {
+    let mut frame = Frame::new(area.width, area.height);
+    let mut draw = |point: Point, c: char, colour: Colour| {
+        if let Some(x) = point.x.checked_sub(area.left) {
+            frame.draw(x, point.y, c, Some(colour));
+        }
+    };
+
+    for (point, tile) in cave.iter() {
+        match tile {
+            Tile::Rock => draw(point, '#', Colour::Grey),
+            Tile::Sand => draw(point, 'o', Colour::Yellow),
+        }
+    }
+    if let Some(floor) = area.floor {
+        for x in area.left..area.left + area.width {
+            draw(Point::new(x, floor), '#', Colour::Grey);
+        }
+    }
+    if !cave.contains(SAND_FALL_START) {
+        draw(SAND_FALL_START, '+', Colour::Cyan);
+    }
+    draw(newest, 'o', Colour::White);
+
+    frame.caption = format!("{} grains of sand at rest", at_rest);
+    frame
+}
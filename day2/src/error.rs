@@ -0,0 +1,87 @@
+use std::fmt;
+
+use common::{parse, Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 2, or reading a rules table.
+#[derive(Debug)]
+pub enum Error {
+    /// `expected` lists the letters the rules allow, e.g. "A, B or C"
+    UnknownOpponentChoice { span: Span, token: String, expected: String },
+    UnknownResponse { span: Span, token: String, expected: String },
+    MissingResponse { span: Span },
+    /// Part one needs the second column to be a shape, but the rules only use this letter
+    /// for an outcome.
+    NotAShape { span: Span },
+    /// Part two needs the second column to be an outcome, but the rules only use this letter
+    /// for a shape.
+    NotAnOutcome { span: Span },
+    InvalidPoints { span: Span, token: String },
+    DuplicateShape { span: Span, name: String },
+    DuplicateLetter { span: Span, token: String },
+    DuplicateOutcome { span: Span },
+    MissingOutcome { outcome: &'static str },
+    /// The shapes can't go round in a cycle where each beats as many as it loses to.
+    ShapeCount { count: usize },
+    Syntax(parse::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownOpponentChoice { token, expected, .. } => {
+                write!(f, "unknown opponent weapon choice `{}`, expected {}", token, expected)
+            }
+            Error::UnknownResponse { token, expected, .. } => {
+                write!(f, "unknown response `{}`, expected {}", token, expected)
+            }
+            Error::MissingResponse { .. } => write!(f, "round is missing a response"),
+            Error::NotAShape { .. } => write!(f, "this response isn't a shape to throw"),
+            Error::NotAnOutcome { .. } => write!(f, "this response isn't how the round ends"),
+            Error::InvalidPoints { token, .. } => {
+                write!(f, "expected the points the shape scores, found `{}`", token)
+            }
+            Error::DuplicateShape { name, .. } => {
+                write!(f, "there's already a shape called `{}`", name)
+            }
+            Error::DuplicateLetter { token, .. } => {
+                write!(f, "`{}` already stands for something else in this column", token)
+            }
+            Error::DuplicateOutcome { .. } => write!(f, "this outcome already has a letter"),
+            Error::MissingOutcome { outcome } => {
+                write!(f, "no letter is given for `{}`", outcome)
+            }
+            Error::ShapeCount { count } => write!(
+                f,
+                "a game needs an odd number of shapes, at least 3, but the rules have {}",
+                count
+            ),
+            Error::Syntax(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::UnknownOpponentChoice { span, .. }
+            | Error::UnknownResponse { span, .. }
+            | Error::MissingResponse { span }
+            | Error::NotAShape { span }
+            | Error::NotAnOutcome { span }
+            | Error::InvalidPoints { span, .. }
+            | Error::DuplicateShape { span, .. }
+            | Error::DuplicateLetter { span, .. }
+            | Error::DuplicateOutcome { span } => Some(*span),
+            Error::Syntax(err) => err.span(),
+            Error::MissingOutcome { .. } | Error::ShapeCount { .. } => None,
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err: parse::Error) -> Self {
+        Error::Syntax(err)
+    }
+}
@@ -0,0 +1,504 @@
+//! Day 2: Rock Paper Scissors.
+//!
+//! A strategy guide lists one round per line: the opponent's shape (`A`, `B` or `C`) and a
+//! second column (`X`, `Y` or `Z`) that the two parts read differently.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use common::parse::{self, Cursor, Line};
+use common::{explain, Answer, Solution, Span};
+
+pub use error::Error;
+
+/// A shape either player can throw, as its place in the [`Rules`]' cycle of shapes. Shapes
+/// only come from the rules, so they're always one of theirs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shape(usize);
+
+/// Who won a round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundResult {
+    Opponent,
+    Me,
+    Draw,
+}
+
+/// One of the shapes in a game, and how the strategy guide writes it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShapeRule {
+    pub name: String,
+    /// What throwing it scores
+    pub points: u32,
+    /// The letter in the first column for the opponent throwing it
+    pub opponent: String,
+    /// The letter in the second column for me throwing it, in part one
+    pub mine: String,
+}
+
+/// How the game is played and scored, and how the strategy guide is written.
+///
+/// The shapes go round in a cycle, where each one beats the half of the others just before
+/// it and loses to the half just after it. That's why there has to be an odd number of
+/// them, e.g. Rock, Paper, Scissors, or Rock, Spock, Paper, Lizard, Scissors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    // At least three of them, in the order of the cycle
+    shapes: Vec<ShapeRule>,
+    // The letter in the second column for each way a round can end, in part two
+    outcomes: Vec<(String, RoundResult)>,
+}
+
+// How each outcome is written in a rules table
+const OUTCOMES: [(&str, RoundResult); 3] = [
+    ("lose", RoundResult::Opponent),
+    ("draw", RoundResult::Draw),
+    ("win", RoundResult::Me),
+];
+
+impl Rules {
+    /// Rock, Paper, Scissors, the way the puzzle plays it.
+    pub fn standard() -> Rules {
+        let shape = |name: &str, points, opponent: &str, mine: &str| ShapeRule {
+            name: String::from(name),
+            points,
+            opponent: String::from(opponent),
+            mine: String::from(mine),
+        };
+        Rules {
+            shapes: vec![
+                shape("Rock", 1, "A", "X"),
+                shape("Paper", 2, "B", "Y"),
+                shape("Scissors", 3, "C", "Z"),
+            ],
+            outcomes: vec![
+                (String::from("X"), RoundResult::Opponent),
+                (String::from("Y"), RoundResult::Draw),
+                (String::from("Z"), RoundResult::Me),
+            ],
+        }
+    }
+
+    /// Reads a rules table. Each shape is a line of its name, its points, and its letters in
+    /// the first and second columns, in the order of the cycle. The letters part two reads
+    /// in the second column are lines of `lose`, `draw` or `win` and the letter:
+    ///
+    /// ```text
+    /// Rock 1 A X
+    /// Paper 2 B Y
+    /// Scissors 3 C Z
+    /// lose X
+    /// draw Y
+    /// win Z
+    /// ```
+    pub fn parse(input: &str) -> Result<Rules, Error> {
+        let mut shapes = Vec::new();
+        let mut outcomes = Vec::new();
+        // The letters used so far for the opponent's shapes, for mine and for the outcomes, so
+        // that none stands for two things at once
+        let mut letters: [Vec<String>; 3] = Default::default();
+
+        for line in parse::lines(input).filter(|line| !line.is_blank()) {
+            let mut cursor = line.cursor();
+            let mut new_letter = |cursor: &mut Cursor, column: usize| -> Result<String, Error> {
+                let letter = cursor.token()?;
+                if letters[column].iter().any(|seen| seen == letter) {
+                    return Err(Error::DuplicateLetter {
+                        span: line.span_of(letter),
+                        token: letter.to_string(),
+                    });
+                }
+                letters[column].push(letter.to_string());
+                Ok(letter.to_string())
+            };
+
+            let name = cursor.token()?;
+            if shapes.iter().any(|shape: &ShapeRule| shape.name == name) {
+                return Err(Error::DuplicateShape {
+                    span: line.span_of(name),
+                    name: name.to_string(),
+                });
+            }
+            match OUTCOMES.iter().find(|(outcome, _)| *outcome == name) {
+                Some(&(_, result)) if outcomes.iter().any(|&(_, seen)| seen == result) => {
+                    return Err(Error::DuplicateOutcome { span: line.span_of(name) });
+                }
+                Some(&(_, result)) => outcomes.push((new_letter(&mut cursor, 2)?, result)),
+                None => {
+                    cursor.skip_whitespace();
+                    let points = cursor.integer().map_err(|err| Error::InvalidPoints {
+                        span: err.span,
+                        token: err.found,
+                    })?;
+                    let opponent = new_letter(&mut cursor, 0)?;
+                    let mine = new_letter(&mut cursor, 1)?;
+                    shapes.push(ShapeRule { name: name.to_string(), points, opponent, mine });
+                }
+            }
+            cursor.skip_whitespace();
+            cursor.end()?;
+        }
+
+        if shapes.len() < 3 || shapes.len() % 2 == 0 {
+            return Err(Error::ShapeCount { count: shapes.len() });
+        }
+        if let Some(&(name, _)) = OUTCOMES.iter().find(|(_, result)| {
+            !outcomes.iter().any(|(_, seen)| seen == result)
+        }) {
+            return Err(Error::MissingOutcome { outcome: name });
+        }
+        Ok(Rules { shapes, outcomes })
+    }
+
+    pub fn shapes(&self) -> &[ShapeRule] {
+        &self.shapes
+    }
+
+    /// The shape called `name`, if there is one.
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.shapes.iter().position(|shape| shape.name == name).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0].name
+    }
+
+    /// Whether `shape` beats `other`, by being at most half the cycle after it.
+    pub fn beats(&self, shape: Shape, other: Shape) -> bool {
+        let count = self.shapes.len();
+        let after = (shape.0 + count - other.0) % count;
+        (1..=count / 2).contains(&after)
+    }
+
+    /// The shape to throw against `opponent` for the round to end in `result`. When several
+    /// would, it's whichever scores the most, or the first of those.
+    pub fn shape_for(&self, opponent: Shape, result: RoundResult) -> Shape {
+        let round = |mine| Round { opponent_choice: opponent, my_choice: mine };
+        (0..self.shapes.len())
+            .map(Shape)
+            .filter(|&mine| round(mine).result(self) == result)
+            .rev()
+            .max_by_key(|mine| self.shapes[mine.0].points)
+            // NOTE: There are always at least three shapes, so every result can be had
+            .unwrap_or(opponent)
+    }
+}
+
+// Letters written out for an error, e.g. "A, B or C", with each only once
+fn either<'a>(letters: impl Iterator<Item = &'a str>) -> String {
+    let mut unique = Vec::new();
+    for letter in letters {
+        if !unique.contains(&letter) {
+            unique.push(letter);
+        }
+    }
+    match unique.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+/// The shapes both players threw in one round.
+#[derive(Debug)]
+pub struct Round {
+    pub opponent_choice: Shape,
+    pub my_choice: Shape,
+}
+
+impl Round {
+    pub fn result(&self, rules: &Rules) -> RoundResult {
+        if self.opponent_choice == self.my_choice {
+            RoundResult::Draw
+        } else if rules.beats(self.opponent_choice, self.my_choice) {
+            RoundResult::Opponent
+        } else {
+            RoundResult::Me
+        }
+    }
+
+    /// My score for the round: points for the shape I threw plus points for the result.
+    pub fn score(&self, rules: &Rules) -> u64 {
+        let weapon_choice_points = u64::from(rules.shapes[self.my_choice.0].points);
+
+        weapon_choice_points + match self.result(rules) {
+            RoundResult::Opponent => 0,
+            RoundResult::Draw => 3,
+            RoundResult::Me => 6,
+        }
+    }
+}
+
+/// The second column of the strategy guide, which part one reads as my weapon
+/// choice and part two reads as how the round needs to end. In games of more than three
+/// shapes a letter may only mean one of those.
+#[derive(Clone, Debug)]
+pub struct Response {
+    pub shape: Option<Shape>,
+    pub outcome: Option<RoundResult>,
+    /// Where the letter is, for a part that finds it means nothing to it
+    pub span: Span,
+}
+
+/// One line of the strategy guide.
+#[derive(Debug)]
+pub struct GuideEntry {
+    pub opponent_choice: Shape,
+    pub response: Response,
+}
+
+/// Parses the input into one [`GuideEntry`] per round, playing by the [standard
+/// rules](Rules::standard).
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<GuideEntry>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse_guide(input, &Rules::standard())
+    }
+
+    fn part_one(guide: &Self::Input) -> Result<Answer, Error> {
+        part_one(guide, &Rules::standard()).map(Answer::from)
+    }
+
+    fn part_two(guide: &Self::Input) -> Result<Answer, Error> {
+        part_two(guide, &Rules::standard()).map(Answer::from)
+    }
+}
+
+/// Reads a strategy guide written with the letters of `rules`.
+pub fn parse_guide(input: &str, rules: &Rules) -> Result<Vec<GuideEntry>, Error> {
+    parse::lines(input)
+        .filter(|round| !round.is_blank())
+        .map(|round| {
+            let mut cursor = round.cursor();
+
+            let (opponent_choice, token) = letter(&round, &mut cursor, |letter| {
+                rules.shapes.iter().position(|shape| shape.opponent == letter).map(Shape)
+            });
+            let opponent_choice = opponent_choice.ok_or_else(|| Error::UnknownOpponentChoice {
+                span: round.span_of(token),
+                token: token.to_string(),
+                expected: either(rules.shapes.iter().map(|shape| &*shape.opponent)),
+            })?;
+
+            let (response, token) = letter(&round, &mut cursor, |letter| {
+                let shape = rules.shapes.iter().position(|shape| shape.mine == letter);
+                let outcome = rules.outcomes.iter().find(|(outcome, _)| outcome == letter);
+                Some(Response {
+                    shape: shape.map(Shape),
+                    outcome: outcome.map(|&(_, result)| result),
+                    span: round.span_of(letter),
+                })
+                .filter(|response| response.shape.is_some() || response.outcome.is_some())
+            });
+            let response = response.ok_or_else(|| match token {
+                "" => Error::MissingResponse { span: round.end() },
+                token => {
+                    let shapes = rules.shapes.iter().map(|shape| &*shape.mine);
+                    let outcomes = rules.outcomes.iter().map(|(letter, _)| &**letter);
+                    Error::UnknownResponse {
+                        span: round.span_of(token),
+                        token: token.to_string(),
+                        expected: either(shapes.chain(outcomes)),
+                    }
+                }
+            })?;
+
+            cursor.skip_whitespace();
+            cursor.end()?;
+
+            Ok(GuideEntry { opponent_choice, response })
+        })
+        .collect()
+}
+
+// The next word and what it stands for, if anything. The word is empty at the end of the
+// line.
+fn letter<'a, T>(
+    line: &Line<'a>,
+    cursor: &mut Cursor<'a>,
+    stands_for: impl Fn(&'a str) -> Option<T>,
+) -> (Option<T>, &'a str) {
+    match cursor.token() {
+        Ok(token) => (stands_for(token), token),
+        Err(_) => (None, &line.text[line.text.len()..]),
+    }
+}
+
+/// The total score when the second column is the shape to play.
+pub fn part_one(guide: &[GuideEntry], rules: &Rules) -> Result<u64, Error> {
+    guide
+        .iter()
+        .enumerate()
+        .map(|(i, GuideEntry { opponent_choice, response })| {
+            let my_choice = response.shape.ok_or(Error::NotAShape { span: response.span })?;
+
+            let round = Round { opponent_choice: *opponent_choice, my_choice };
+            Ok(explain_round(i + 1, round, rules))
+        })
+        .sum()
+}
+
+// The round's score, noting down how it went
+fn explain_round(number: usize, round: Round, rules: &Rules) -> u64 {
+    let score = round.score(rules);
+    explain!(
+        "round",
+        [
+            round = number,
+            shape = rules.name(round.my_choice),
+            opponent = rules.name(round.opponent_choice),
+            score = score,
+        ],
+        "{round}: {shape} against {opponent} scores {score}"
+    );
+    score
+}
+
+/// The total score when the second column is how the round has to end.
+pub fn part_two(guide: &[GuideEntry], rules: &Rules) -> Result<u64, Error> {
+    guide
+        .iter()
+        .enumerate()
+        .map(|(i, GuideEntry { opponent_choice, response })| {
+            let outcome = response.outcome.ok_or(Error::NotAnOutcome { span: response.span })?;
+            let my_choice = rules.shape_for(*opponent_choice, outcome);
+
+            let round = Round { opponent_choice: *opponent_choice, my_choice };
+            Ok(explain_round(i + 1, round, rules))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    const RPSLS: &str = include_str!("rpsls.txt");
+
+    #[test]
+    fn round_score_adds_the_shape_and_the_outcome() {
+        let rules = Rules::standard();
+        let shape = |name| rules.shape(name).unwrap();
+        let (rock, paper, scissors) = (shape("Rock"), shape("Paper"), shape("Scissors"));
+        let win = Round { opponent_choice: rock, my_choice: paper };
+        let loss = Round { opponent_choice: paper, my_choice: rock };
+        let draw = Round { opponent_choice: scissors, my_choice: scissors };
+
+        assert_eq!(win.score(&rules), 2 + 6);
+        assert_eq!(loss.score(&rules), 1);
+        assert_eq!(draw.score(&rules), 3 + 3);
+    }
+
+    #[test]
+    fn rules_table_can_describe_the_standard_game() {
+        let table = "Rock 1 A X\nPaper 2 B Y\nScissors 3 C Z\nlose X\ndraw Y\nwin Z\n";
+        assert_eq!(Rules::parse(table).unwrap(), Rules::standard());
+    }
+
+    #[test]
+    fn every_shape_beats_half_the_others() {
+        let rules = Rules::parse(RPSLS).unwrap();
+        let shape = |name| rules.shape(name).unwrap();
+        let beats = |a, b| rules.beats(shape(a), shape(b));
+
+        assert!(beats("Rock", "Scissors") && beats("Rock", "Lizard"));
+        assert!(beats("Spock", "Rock") && beats("Spock", "Scissors"));
+        assert!(beats("Paper", "Spock") && beats("Paper", "Rock"));
+        assert!(beats("Lizard", "Paper") && beats("Lizard", "Spock"));
+        assert!(beats("Scissors", "Lizard") && beats("Scissors", "Paper"));
+        let names = rules.shapes().iter().map(|shape| &*shape.name).collect::<Vec<_>>();
+        for a in &names {
+            assert_eq!(names.iter().filter(|b| beats(a, b)).count(), 2);
+            assert!(!beats(a, a));
+        }
+
+        let round = Round { opponent_choice: shape("Spock"), my_choice: shape("Lizard") };
+        assert_eq!(round.result(&rules), RoundResult::Me);
+        assert_eq!(round.score(&rules), 4 + 6);
+    }
+
+    #[test]
+    fn variant_guides_are_read_with_their_rules_letters() {
+        let rules = Rules::parse(RPSLS).unwrap();
+        // Paper against Rock, Lizard against Scissors, and Scissors against Paper
+        let guide = parse_guide("A X\nE Y\nC Z\n", &rules).unwrap();
+        assert_eq!(part_one(&guide, &rules).unwrap(), (3 + 6) + 4 + (5 + 6));
+
+        // Losing to Rock and beating Paper can both be done with Lizard or Scissors, and
+        // Scissors scores more
+        assert_eq!(part_two(&guide, &rules).unwrap(), 5 + (5 + 3) + (5 + 6));
+
+        // V and W are only shapes, so part two can't read them
+        let guide = parse_guide("A W\n", &rules).unwrap();
+        assert_eq!(part_one(&guide, &rules).unwrap(), 2 + 6);
+        assert!(matches!(part_two(&guide, &rules), Err(Error::NotAnOutcome { .. })));
+
+        match parse_guide("F X\n", &rules) {
+            Err(Error::UnknownOpponentChoice { expected, .. }) => {
+                assert_eq!(expected, "A, B, C, D or E");
+            }
+            other => panic!("expected an unknown opponent choice, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rules_table_rejects_games_that_cant_be_played() {
+        let even = "Rock 1 A X\nPaper 2 B Y\nlose X\ndraw Y\nwin Z\n";
+        assert!(matches!(Rules::parse(even), Err(Error::ShapeCount { count: 2 })));
+
+        let twice = "Rock 1 A X\nPaper 2 A Y\nScissors 3 C Z\nlose X\ndraw Y\nwin Z\n";
+        match Rules::parse(twice) {
+            Err(Error::DuplicateLetter { span, token }) => {
+                assert_eq!((span.line, span.column, token.as_str()), (2, 9, "A"));
+            }
+            other => panic!("expected a duplicate letter, got {:?}", other),
+        }
+
+        let no_win = "Rock 1 A X\nPaper 2 B Y\nScissors 3 C Z\nlose X\ndraw Y\n";
+        assert!(matches!(Rules::parse(no_win), Err(Error::MissingOutcome { outcome: "win" })));
+
+        let same = "Rock 1 A X\nPaper 2 B Y\nRock 3 C Z\nlose X\ndraw Y\nwin Z\n";
+        match Rules::parse(same) {
+            Err(Error::DuplicateShape { span, name }) => {
+                assert_eq!((span.line, span.column, name.as_str()), (3, 1, "Rock"));
+            }
+            other => panic!("expected a duplicate shape, got {:?}", other),
+        }
+
+        let points = "Rock one A X\n";
+        assert!(matches!(Rules::parse(points), Err(Error::InvalidPoints { .. })));
+    }
+
+    #[test]
+    fn part_one_example() {
+        let guide = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part_one(&guide).unwrap(), Answer::Unsigned(15));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let guide = Day2::parse(EXAMPLE).unwrap();
+        assert_eq!(Day2::part_two(&guide).unwrap(), Answer::Unsigned(12));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day2>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day2>(stress::generate, stress::brute_force, 100);
+    }
+}
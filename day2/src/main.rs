@@ -1,113 +1,94 @@
-#[derive(Clone, Debug, PartialEq)]
-enum Shape {
-    Rock,
-    Paper,
-    Scissor,
-}
+use std::env;
+use std::fs;
+use std::process::ExitCode;
 
-impl Shape {
-    fn defeats(&self) -> Shape {
-        match self {
-            Shape::Rock => Shape::Scissor,
-            Shape::Paper => Shape::Rock,
-            Shape::Scissor => Shape:: Paper
-        }
-    }
+use common::diagnostic;
+use common::input::Source;
+use day2::{parse_guide, part_one, part_two, Day2, Rules};
 
-    fn defeated_by(&self) -> Shape {
-        match self {
-            Shape::Rock => Shape::Paper,
-            Shape::Paper => Shape::Scissor,
-            Shape::Scissor => Shape:: Rock
-        }
-    }
-}
+const USAGE: &str = "\
+Usage:
+    day2 [<PATH> | -] [--format <text|json>] [--explain]
+    day2 play <RULES> [<PATH> | -]
 
-#[derive(Debug)]
-enum RoundResult {
-    Opponent,
-    Me,
-    Draw,
-}
+'play' scores a strategy guide for a game described by the rules table at RULES instead of
+Rock, Paper, Scissors. Each shape is a line of its name, its points, and the letters for it
+in the first and second columns, in the order they beat each other. The letters for how a
+round ends are lines of 'lose', 'draw' or 'win' and the letter:
 
-#[derive(Debug)]
-struct Round {
-    opponent_choice: Shape,
-    my_choice: Shape,
-}
+    Rock 1 A V
+    Spock 2 B W
+    Paper 3 C X
+    Lizard 4 D Y
+    Scissors 5 E Z
+    lose X
+    draw Y
+    win Z";
 
-impl Round {
-    fn result(&self) -> RoundResult {
-        if self.opponent_choice == self.my_choice {
-            RoundResult::Draw
-        } else if self.opponent_choice.defeats() == self.my_choice {
-            RoundResult::Opponent
-        } else {
-            RoundResult::Me
-        }
-    }
-
-    fn score(&self) -> u32 {
-        let weapon_choice_points =  match self.my_choice {
-            Shape::Rock => 1,
-            Shape::Paper => 2,
-            Shape::Scissor => 3,
-        };
-
-        weapon_choice_points + match self.result() {
-            RoundResult::Opponent => 0,
-            RoundResult::Draw => 3,
-            RoundResult::Me => 6,
-        }
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("play") => play(&args[1..]),
+        _ => common::run::main::<Day2>(),
     }
 }
 
-fn main() {
-    let input = include_str!("./input.txt");
-    println!("Part 1: {}", part_one(input));
-    println!("Part 2: {}", part_two(input));
-}
+fn play(args: &[String]) -> ExitCode {
+    let (rules_path, path) = match args {
+        [rules] => (rules, None),
+        [rules, path] => (rules, Some(path.as_str())),
+        _ => return usage_error("'play' expects a rules table, then the input"),
+    };
 
-fn part_one(input: &str) -> u32 {
-    input.lines().map(|round| {
-        let results: Vec<&str> = round.split(" ").collect();
-        let opponent_choice = match results[0] {
-            "A" => Shape::Rock,
-            "B" => Shape::Paper,
-            "C" => Shape::Scissor,
-            x => panic!("Unknown opponent weapon choice: {}", x),
-        };
+    let rules_text = match fs::read_to_string(rules_path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("error: couldn't read rules table '{}': {}", rules_path, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let rules = match Rules::parse(&rules_text) {
+        Ok(rules) => rules,
+        Err(err) => {
+            eprint!("{}", diagnostic::render(&err, rules_path, &rules_text));
+            return ExitCode::FAILURE;
+        }
+    };
 
-        let my_choice = match results[1] {
-            "X" => Shape::Rock,
-            "Y" => Shape::Paper,
-            "Z" => Shape::Scissor,
-            x => panic!("Unknown my weapon choice: {}", x),
-        };
+    let input = match Source::from_arg(2, path).and_then(|source| source.load(2)) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let guide = match parse_guide(&input.text, &rules) {
+        Ok(guide) => guide,
+        Err(err) => {
+            eprint!("{}", diagnostic::render(&err, &input.name, &input.text));
+            return ExitCode::FAILURE;
+        }
+    };
 
-        Round { opponent_choice, my_choice }.score()
-    })
-    .sum()
+    // Each part only fails on letters it can't read, so one can still be scored without the
+    // other
+    let mut failed = false;
+    for (part, score) in [(1, part_one(&guide, &rules)), (2, part_two(&guide, &rules))] {
+        match score {
+            Ok(score) => println!("Part {}: {}", part, score),
+            Err(err) => {
+                eprint!("{}", diagnostic::render(&err, &input.name, &input.text));
+                failed = true;
+            }
+        }
+    }
+    match failed {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
 }
 
-fn part_two(input: &str) -> u32 {
-    input.lines().map(|round| {
-        let results: Vec<&str> = round.split(" ").collect();
-        let opponent_choice = match results[0] {
-            "A" => Shape::Rock,
-            "B" => Shape::Paper,
-            "C" => Shape::Scissor,
-            x => panic!("Unknown opponent weapon choice: {}", x),
-        };
-
-        let choice_to_make = match results[1] {
-            "X" => opponent_choice.defeats(),
-            "Y" => opponent_choice.clone(),
-            "Z" => opponent_choice.defeated_by(),
-            x => panic!("Unknown strategy: {}", x),
-        };
-
-        Round { opponent_choice, my_choice: choice_to_make }.score()
-    })
-    .sum()
+fn usage_error(message: &str) -> ExitCode {
+    eprintln!("error: {}\n\n{}", message, USAGE);
+    ExitCode::FAILURE
 }
@@ -0,0 +1,45 @@
+//! Random strategy guides, and a brute force solver to check answers against.
+
+use common::{Answer, Rng};
+
+/// A strategy guide of `size` rounds.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{} {}\n", rng.pick(&["A", "B", "C"]), rng.pick(&["X", "Y", "Z"])))
+        .collect()
+}
+
+// The score of every possible line of the guide, worked out by hand from the rules
+const PART_ONE_SCORES: [(&str, u64); 9] = [
+    ("A X", 4),
+    ("A Y", 8),
+    ("A Z", 3),
+    ("B X", 1),
+    ("B Y", 5),
+    ("B Z", 9),
+    ("C X", 7),
+    ("C Y", 2),
+    ("C Z", 6),
+];
+
+const PART_TWO_SCORES: [(&str, u64); 9] = [
+    ("A X", 3),
+    ("A Y", 4),
+    ("A Z", 8),
+    ("B X", 1),
+    ("B Y", 5),
+    ("B Z", 9),
+    ("C X", 2),
+    ("C Y", 6),
+    ("C Z", 7),
+];
+
+/// Looks every round up in a table of all nine possible rounds.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let total = |scores: &[(&str, u64)]| {
+        let score = |line| scores.iter().find(|(round, _)| *round == line).unwrap().1;
+        Answer::from(input.lines().map(score).sum::<u64>())
+    };
+
+    [Some(total(&PART_ONE_SCORES)), Some(total(&PART_TWO_SCORES))]
+}
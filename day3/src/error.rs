@@ -0,0 +1,35 @@
+use std::fmt;
+
+use common::{Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 3.
+#[derive(Debug)]
+pub enum Error {
+    UnknownItem { span: Span, token: String },
+    NoSharedItem { span: Span },
+    IncompleteGroup { span: Span },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownItem { token, .. } => {
+                write!(f, "unknown item type `{}`, expected a letter", token)
+            }
+            Error::NoSharedItem { .. } => write!(f, "no item type is shared"),
+            Error::IncompleteGroup { .. } => write!(f, "group has fewer than three elves"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::UnknownItem { span, .. }
+            | Error::NoSharedItem { span }
+            | Error::IncompleteGroup { span } => Some(*span),
+        }
+    }
+}
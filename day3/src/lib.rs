@@ -0,0 +1,162 @@
+//! Day 3: Rucksack Reorganization.
+//!
+//! Each line is a rucksack, with the first half of its items in one compartment and the
+//! second half in the other. Items are letters, see [`get_item_priority`].
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use common::{explain, parse, Answer, Solution, Span};
+
+pub use error::Error;
+
+/// Parses the input into one rucksack per line, after checking that every item is a letter.
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    // One rucksack per line, so a rucksack's index is its line number minus one
+    type Input = Vec<String>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse::lines(input)
+            .map(|rucksack| {
+                // Validate the items up front so the solvers only ever see known item types
+                let mut cursor = rucksack.cursor();
+                let items = cursor.take_while(|item| get_item_priority(item).is_some());
+
+                if let Some(item) = cursor.rest().chars().next() {
+                    let token = &cursor.rest()[..item.len_utf8()];
+                    return Err(Error::UnknownItem {
+                        span: rucksack.span_of(token),
+                        token: token.to_string(),
+                    });
+                }
+
+                Ok(String::from(items))
+            })
+            .collect()
+    }
+
+    fn part_one(rucksacks: &Self::Input) -> Result<Answer, Error> {
+        part_one(rucksacks).map(Answer::from)
+    }
+
+    fn part_two(rucksacks: &Self::Input) -> Result<Answer, Error> {
+        part_two(rucksacks).map(Answer::from)
+    }
+}
+
+/// The priority of an item type, or `None` if it isn't a letter.
+pub fn get_item_priority(item: char) -> Option<u32> {
+    // NOTE: Lowercase item types a through z have priorities 1 through 26.
+    // Uppercase item types A through Z have priorities 27 through 52.
+    match item {
+        'A'..='Z' => Some((item as u32) - 38),
+        'a'..='z' => Some((item as u32) - 96),
+        _ => None,
+    }
+}
+
+/// The sum of the priorities of the item found in both compartments of each rucksack.
+///
+/// NOTE: The sums are `u64`, since it only takes about 80 million rucksacks to go past what
+/// fits in a `u32`.
+pub fn part_one(rucksacks: &[String]) -> Result<u64, Error> {
+    rucksacks.iter().enumerate().map(|(i, rucksack)| {
+        let compartment_size = rucksack.chars().count() / 2;
+        let (first_compartment, second_compartment) = rucksack.split_at(compartment_size);
+
+        for item in first_compartment.chars() {
+            if second_compartment.contains(item) {
+                if let Some(priority) = get_item_priority(item) {
+                    explain!(
+                        "rucksack",
+                        [rucksack = i + 1, item = item, priority = priority],
+                        "{rucksack} has {item} in both compartments, worth {priority}"
+                    );
+                    return Ok(u64::from(priority));
+                }
+            }
+        }
+
+        Err(Error::NoSharedItem { span: Span::line(i + 1, rucksack) })
+    }).sum()
+}
+
+/// The sum of the priorities of the badge shared by each group of three rucksacks.
+pub fn part_two(rucksacks: &[String]) -> Result<u64, Error> {
+    rucksacks
+        .chunks(3)
+        .enumerate()
+        .map(|(i, group)| {
+            let line_number = i * 3 + 1;
+            let (first_rucksack, second_rucksack, third_rucksack) = match group {
+                [first, second, third] => (first, second, third),
+                _ => {
+                    return Err(Error::IncompleteGroup {
+                        span: Span::line(line_number, &group[0]),
+                    })
+                }
+            };
+
+            for item in first_rucksack.chars() {
+                if second_rucksack.contains(item) && third_rucksack.contains(item) {
+                    if let Some(priority) = get_item_priority(item) {
+                        explain!(
+                            "group",
+                            [group = i + 1, badge = item, priority = priority],
+                            "{group} carries {badge} as its badge, worth {priority}"
+                        );
+                        return Ok(u64::from(priority));
+                    }
+                }
+            }
+
+            Err(Error::NoSharedItem { span: Span::line(line_number, first_rucksack) })
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn item_priorities() {
+        assert_eq!(get_item_priority('a'), Some(1));
+        assert_eq!(get_item_priority('z'), Some(26));
+        assert_eq!(get_item_priority('A'), Some(27));
+        assert_eq!(get_item_priority('Z'), Some(52));
+        assert_eq!(get_item_priority('1'), None);
+    }
+
+    #[test]
+    fn part_one_example() {
+        let rucksacks = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part_one(&rucksacks).unwrap(), Answer::Unsigned(157));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let rucksacks = Day3::parse(EXAMPLE).unwrap();
+        assert_eq!(Day3::part_two(&rucksacks).unwrap(), Answer::Unsigned(70));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day3>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day3>(stress::generate, stress::brute_force, 30);
+    }
+}
@@ -0,0 +1,102 @@
+//! Random rucksacks, and a brute force solver to check answers against.
+
+use common::{Answer, Rng};
+
+const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// `size` groups of three rucksacks. As in the puzzle, each rucksack has exactly one item
+/// type in both compartments and each group has exactly one item type in all three.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let items = ITEMS.chars().collect::<Vec<char>>();
+    let mut rucksacks = String::new();
+
+    for _ in 0..size {
+        let badge = *rng.pick(&items);
+
+        // Every other item type can go in at most two of the rucksacks, and only ever in one
+        // of a rucksack's compartments
+        let mut allowed = vec![(Vec::new(), Vec::new()); 3];
+        for &item in items.iter().filter(|&&item| item != badge) {
+            let left_out = rng.below(3);
+            for (i, (first, second)) in allowed.iter_mut().enumerate() {
+                match (i == left_out, rng.one_in(2)) {
+                    (true, _) => {}
+                    (false, true) => first.push(item),
+                    (false, false) => second.push(item),
+                }
+            }
+        }
+
+        for (first, second) in allowed {
+            let mut kinds = first.iter().chain(&second).copied().collect::<Vec<char>>();
+            kinds.push(badge);
+            let shared = *rng.pick(&kinds);
+            let badge_first = rng.one_in(2);
+
+            let length = rng.between(2, 16);
+            let mut required = (vec![shared], vec![shared]);
+            match badge_first {
+                true => required.0.push(badge),
+                false => required.1.push(badge),
+            }
+            required.0.dedup();
+            required.1.dedup();
+
+            rucksacks.extend(compartment(rng, length, &first, &required.0));
+            rucksacks.extend(compartment(rng, length, &second, &required.1));
+            rucksacks.push('\n');
+        }
+    }
+    rucksacks
+}
+
+// `length` items drawn from `allowed`, including every one of `required`, in a random order
+fn compartment(rng: &mut Rng, length: usize, allowed: &[char], required: &[char]) -> Vec<char> {
+    let mut items = required.to_vec();
+    while items.len() < length {
+        items.push(*rng.pick(if allowed.is_empty() { required } else { allowed }));
+    }
+    for i in (1..items.len()).rev() {
+        items.swap(i, rng.below(i + 1));
+    }
+    items
+}
+
+fn priority(item: char) -> u64 {
+    ITEMS.chars().position(|kind| kind == item).unwrap() as u64 + 1
+}
+
+// The total priority of every item type found in all of `rucksacks`, or `None` if there
+// isn't one
+fn shared_priority(rucksacks: &[&str]) -> Option<u64> {
+    let shared = ITEMS
+        .chars()
+        .filter(|&item| rucksacks.iter().all(|rucksack| rucksack.contains(item)))
+        .map(priority)
+        .collect::<Vec<u64>>();
+    (!shared.is_empty()).then(|| shared.iter().sum())
+}
+
+/// Tries every item type against both compartments of each rucksack, and against each
+/// group of three rucksacks.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let rucksacks = input.lines().collect::<Vec<&str>>();
+
+    let part_one = rucksacks
+        .iter()
+        .map(|rucksack| {
+            let (first, second) = rucksack.split_at(rucksack.len() / 2);
+            shared_priority(&[first, second])
+        })
+        .sum::<Option<u64>>();
+
+    let part_two = rucksacks
+        .chunks(3)
+        .map(|group| match group.len() {
+            3 => shared_priority(group),
+            _ => None,
+        })
+        .sum::<Option<u64>>();
+
+    [part_one.map(Answer::from), part_two.map(Answer::from)]
+}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+use common::{parse, Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 4.
+#[derive(Debug)]
+pub enum Error {
+    MissingComma { span: Span },
+    MissingDash { span: Span, token: String },
+    InvalidSection { span: Span, token: String },
+    BackwardsRange { span: Span, token: String },
+    Syntax(parse::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingComma { .. } => {
+                write!(f, "expected a pair of assignments separated by `,`")
+            }
+            Error::MissingDash { token, .. } => {
+                write!(f, "assignment `{}` is not a range like `2-4`", token)
+            }
+            Error::InvalidSection { token, .. } => write!(f, "invalid section ID `{}`", token),
+            Error::BackwardsRange { token, .. } => {
+                write!(f, "assignment `{}` ends before it starts", token)
+            }
+            Error::Syntax(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::MissingComma { span }
+            | Error::MissingDash { span, .. }
+            | Error::InvalidSection { span, .. }
+            | Error::BackwardsRange { span, .. } => Some(*span),
+            Error::Syntax(err) => err.span(),
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err: parse::Error) -> Self {
+        Error::Syntax(err)
+    }
+}
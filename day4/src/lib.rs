@@ -0,0 +1,191 @@
+//! Day 4: Camp Cleanup.
+//!
+//! Each line holds the section assignments of a pair of elves, e.g. `2-4,6-8`.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use std::ops::Range;
+
+use common::parse::{self, Cursor, Line};
+use common::{explain, Answer, Solution};
+
+pub use error::Error;
+
+/// How two section assignments relate to each other.
+pub trait RangeComparison<T> {
+    fn contains_range(&self, other: &Self) -> bool;
+    fn overlaps_with(&self, other: &Self) -> bool;
+}
+
+impl<T: PartialOrd> RangeComparison<T> for Range<T> {
+    fn contains_range(&self, other: &Range<T>) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    fn overlaps_with(&self, other: &Range<T>) -> bool {
+        self.start <= other.end && self.end >= other.end
+            || self.end == other.start
+    }
+}
+
+/// Parses the input into each pair's two assignments. The ranges include their end,
+/// so `2-4` becomes `2..4` and is compared with [`RangeComparison`].
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    // Each pair of elves' section assignments
+    type Input = Vec<(Range<u32>, Range<u32>)>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse::lines(input)
+            .map(|pair| {
+                let mut cursor = pair.cursor();
+                let assignment_one_range = get_assignment_range(&pair, &mut cursor)?;
+                cursor.literal(",").map_err(|err| Error::MissingComma { span: err.span })?;
+                let assignment_two_range = get_assignment_range(&pair, &mut cursor)?;
+                cursor.end()?;
+                Ok((assignment_one_range, assignment_two_range))
+            })
+            .collect()
+    }
+
+    fn part_one(pairs: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_one(pairs).into())
+    }
+
+    fn part_two(pairs: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_two(pairs).into())
+    }
+}
+
+fn get_assignment_range(pair: &Line, cursor: &mut Cursor) -> Result<Range<u32>, Error> {
+    let assignment = cursor.rest().split(',').next().unwrap_or_default();
+
+    let section = |cursor: &mut Cursor| {
+        cursor
+            .integer::<u32>()
+            .map_err(|err| Error::InvalidSection { span: err.span, token: err.found })
+    };
+
+    let start = section(cursor)?;
+    cursor.literal("-").map_err(|_| Error::MissingDash {
+        span: pair.span_of(assignment),
+        token: assignment.to_string(),
+    })?;
+    let end = section(cursor)?;
+
+    if start > end {
+        return Err(Error::BackwardsRange {
+            span: pair.span_of(assignment),
+            token: assignment.to_string(),
+        });
+    }
+
+    Ok(Range { start, end })
+}
+
+/// How many pairs have one assignment entirely containing the other. Counted as a `usize`,
+/// which can't overflow since there can't be more pairs than that.
+pub fn part_one(pairs: &[(Range<u32>, Range<u32>)]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .flat_map(|(i, (assignment_one_range, assignment_two_range))| {
+            if assignment_one_range.contains_range(assignment_two_range)
+                || assignment_two_range.contains_range(assignment_one_range)
+            {
+                explain_pair(i + 1, assignment_one_range, assignment_two_range, "contains");
+                return Some(1);
+            }
+            None
+        })
+        .sum()
+}
+
+/// How many pairs have assignments that overlap at all.
+pub fn part_two(pairs: &[(Range<u32>, Range<u32>)]) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .flat_map(|(i, (assignment_one_range, assignment_two_range))| {
+            if assignment_one_range.overlaps_with(assignment_two_range)
+                || assignment_two_range.overlaps_with(assignment_one_range)
+            {
+                explain_pair(i + 1, assignment_one_range, assignment_two_range, "overlaps");
+                return Some(1);
+            }
+            None
+        })
+        .sum()
+}
+
+// Notes down a pair that counts, as the sections the puzzle writes them as
+fn explain_pair(pair: usize, first: &Range<u32>, second: &Range<u32>, verdict: &str) {
+    explain!(
+        "pair",
+        [
+            pair = pair,
+            first = format!("{}-{}", first.start, first.end),
+            verdict = verdict,
+            second = format!("{}-{}", second.start, second.end),
+        ],
+        "{pair}: {first} {verdict} {second}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn contains_range() {
+        assert!((2..8).contains_range(&(3..7)));
+        assert!((4..6).contains_range(&(6..6)));
+        assert!((2..4).contains_range(&(2..4)));
+        assert!(!(3..7).contains_range(&(2..8)));
+        assert!(!(2..6).contains_range(&(4..8)));
+    }
+
+    #[test]
+    fn overlaps_with_either_way_round() {
+        let overlaps = |a: Range<u32>, b: Range<u32>| a.overlaps_with(&b) || b.overlaps_with(&a);
+
+        assert!(overlaps(5..7, 7..9));
+        assert!(overlaps(2..8, 3..7));
+        assert!(overlaps(6..6, 4..6));
+        assert!(overlaps(2..6, 4..8));
+        assert!(!overlaps(2..4, 6..8));
+        assert!(!overlaps(2..3, 4..5));
+    }
+
+    #[test]
+    fn part_one_example() {
+        let pairs = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part_one(&pairs).unwrap(), Answer::Unsigned(2));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let pairs = Day4::parse(EXAMPLE).unwrap();
+        assert_eq!(Day4::part_two(&pairs).unwrap(), Answer::Unsigned(4));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day4>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day4>(stress::generate, stress::brute_force, 200);
+    }
+}
@@ -0,0 +1,40 @@
+//! Random section assignments, and a brute force solver to check answers against.
+
+use std::collections::HashSet;
+
+use common::{Answer, Rng};
+
+/// A list of `size` pairs of assignments, with sections numbered up to 99 as in the puzzle.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut pairs = String::new();
+    for _ in 0..size {
+        let (a, b) = (rng.between(1, 99), rng.between(1, 99));
+        let (c, d) = (rng.between(1, 99), rng.between(1, 99));
+        pairs += &format!("{}-{},{}-{}\n", a.min(b), a.max(b), c.min(d), c.max(d));
+    }
+    pairs
+}
+
+fn sections(assignment: &str) -> HashSet<u32> {
+    let (start, end) = assignment.split_once('-').unwrap();
+    (start.parse().unwrap()..=end.parse().unwrap()).collect()
+}
+
+/// Lists every section in both assignments and compares the lists.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let pairs = input
+        .lines()
+        .map(|pair| {
+            let (first, second) = pair.split_once(',').unwrap();
+            (sections(first), sections(second))
+        })
+        .collect::<Vec<_>>();
+
+    let containing = pairs
+        .iter()
+        .filter(|(first, second)| first.is_subset(second) || second.is_subset(first))
+        .count();
+    let overlapping = pairs.iter().filter(|(first, second)| !first.is_disjoint(second)).count();
+
+    [Some(containing.into()), Some(overlapping.into())]
+}
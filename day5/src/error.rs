@@ -0,0 +1,53 @@
+use std::fmt;
+
+use common::{Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 5.
+#[derive(Debug)]
+pub enum Error {
+    InvalidStackNumber { span: Span, token: String },
+    MalformedCrate { span: Span, token: String },
+    CrateOutsideStacks { span: Span, token: String },
+    InvalidInstruction { span: Span },
+    InvalidNumber { span: Span, token: String },
+    UnknownStack { span: Span, token: String },
+    NotEnoughCrates { span: Span },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidStackNumber { token, .. } => write!(f, "invalid stack number `{}`", token),
+            Error::MalformedCrate { token, .. } => {
+                write!(f, "malformed crate `{}`, expected a label like `[A]`", token)
+            }
+            Error::CrateOutsideStacks { token, .. } => {
+                write!(f, "crate `{}` isn't above a numbered stack", token)
+            }
+            Error::InvalidInstruction { .. } => {
+                write!(f, "expected an instruction like `move 1 from 2 to 3`")
+            }
+            Error::InvalidNumber { token, .. } => write!(f, "invalid number `{}`", token),
+            Error::UnknownStack { token, .. } => write!(f, "there is no stack `{}`", token),
+            Error::NotEnoughCrates { .. } => {
+                write!(f, "the source stack runs out of crates to move")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidStackNumber { span, .. }
+            | Error::MalformedCrate { span, .. }
+            | Error::CrateOutsideStacks { span, .. }
+            | Error::InvalidInstruction { span }
+            | Error::InvalidNumber { span, .. }
+            | Error::UnknownStack { span, .. }
+            | Error::NotEnoughCrates { span } => Some(*span),
+        }
+    }
+}
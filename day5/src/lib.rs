@@ -0,0 +1,352 @@
+//! Day 5: Supply Stacks.
+//!
+//! A drawing of stacks of crates, then a blank line, then moves like `move 1 from 2 to 1`.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use std::collections::HashMap;
+use std::collections::LinkedList;
+
+use common::parse::{self, Cursor, Expected, Line};
+use common::{explain, Answer, Solution, Span};
+
+pub use error::Error;
+
+/// One move of the procedure: `amount` crates from the top of `source` onto `destination`.
+pub struct Instruction {
+    pub amount: u32,
+    pub source: u32,
+    pub destination: u32,
+    /// Where the instruction came from, in case it can't be carried out
+    pub span: Span,
+}
+
+/// The stacks of crates, numbered from 1 and listed bottom to top, and the moves to make.
+pub struct Procedure {
+    pub stacks: HashMap<u32, LinkedList<char>>,
+    pub instructions: Vec<Instruction>,
+}
+
+/// Parses the input into the starting stacks and the moves to make.
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Procedure;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        // The stack diagram is separated from the instructions by an empty line
+        let blocks = parse::blocks(input);
+        let (diagram, instructions) = match blocks.split_first() {
+            Some((diagram, instructions)) => (&diagram[..], instructions.concat()),
+            None => (&[][..], Vec::new()),
+        };
+
+        let stacks = build_stack(diagram)?;
+        let instructions = build_instructions(&instructions, &stacks)?;
+        Ok(Procedure { stacks, instructions })
+    }
+
+    fn part_one(procedure: &Self::Input) -> Result<Answer, Error> {
+        part_one(procedure).map(Answer::from)
+    }
+
+    fn part_two(procedure: &Self::Input) -> Result<Answer, Error> {
+        part_two(procedure).map(Answer::from)
+    }
+}
+
+fn build_stack(diagram: &[Line]) -> Result<HashMap<u32, LinkedList<char>>, Error> {
+    let mut stacks: HashMap<u32, LinkedList<char>> = HashMap::new();
+
+    // The last line of the diagram has the number of stacks. We'll use these
+    // numbers as the keys for our HashMap to keep track of the stacks.
+    let Some((stack_numbers, rows)) = diagram.split_last() else {
+        return Ok(stacks);
+    };
+    let mut cursor = stack_numbers.cursor();
+    loop {
+        cursor.skip_whitespace();
+        if cursor.is_at_end() {
+            break;
+        }
+        let number = cursor.integer::<u32>().map_err(|err| Error::InvalidStackNumber {
+            span: err.span,
+            token: err.found,
+        })?;
+        stacks.insert(number, LinkedList::new());
+    }
+
+    // Iterate through the diagram from the bottom up so that the stack order is correct.
+    // Starting from the bottom of the stacks, we'll add the crate label characters.
+    // Stacks are numbered from 1, so the first crate on a row belongs to stack 1.
+    for row in rows.iter().rev() {
+        let mut cursor = row.cursor();
+        for current_stack in 1.. {
+            if cursor.rest().trim().is_empty() {
+                break;
+            }
+
+            let start = cursor.rest();
+            let (label, token) = cursor.consumed(crate_slot).map_err(|_| {
+                // Each slot is four characters wide, like "[Z] "
+                let end = start.char_indices().nth(4).map_or(start.len(), |(i, _)| i);
+                let token = start[..end].trim_end();
+                Error::MalformedCrate { span: row.span_of(token), token: token.to_string() }
+            })?;
+
+            // No crate at this height in this stack
+            let Some(label) = label else {
+                continue;
+            };
+            match stacks.get_mut(&current_stack) {
+                Some(stack) => stack.push_back(label),
+                None => {
+                    let token = token.trim_end();
+                    return Err(Error::CrateOutsideStacks {
+                        span: row.span_of(token),
+                        token: token.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+// One slot of a diagram row: a crate like `[Z]`, or three spaces where there's no crate at
+// that height, followed by a space unless it's the last one on the row
+fn crate_slot(cursor: &mut Cursor) -> Result<Option<char>, parse::Error> {
+    let label = match cursor.eat("   ") {
+        true => None,
+        false => {
+            cursor.literal("[")?;
+            let label = cursor.take_while(char::is_alphabetic);
+            let mut chars = label.chars();
+            let (Some(label), None) = (chars.next(), chars.next()) else {
+                return Err(cursor.error(Expected::Token));
+            };
+            cursor.literal("]")?;
+            Some(label)
+        }
+    };
+    if !cursor.is_at_end() {
+        cursor.literal(" ")?;
+    }
+    Ok(label)
+}
+
+fn build_instructions(
+    instructions: &[Line],
+    stacks: &HashMap<u32, LinkedList<char>>,
+) -> Result<Vec<Instruction>, Error> {
+    instructions
+        .iter()
+        .map(|instruction| {
+            let mut cursor = instruction.cursor();
+
+            let number = |cursor: &mut Cursor| {
+                cursor
+                    .integer::<u32>()
+                    .map_err(|err| Error::InvalidNumber { span: err.span, token: err.found })
+            };
+
+            let stack = |cursor: &mut Cursor| {
+                let (stack, token) = cursor.consumed(number)?;
+                match stacks.contains_key(&stack) {
+                    true => Ok(stack),
+                    false => Err(Error::UnknownStack {
+                        span: instruction.span_of(token),
+                        token: token.to_string(),
+                    }),
+                }
+            };
+
+            let literal = |cursor: &mut Cursor, literal| {
+                cursor.literal(literal).map_err(|err| Error::InvalidInstruction { span: err.span })
+            };
+
+            // Every instruction is of the format "move 1 from 8 to 9"
+            literal(&mut cursor, "move ")?;
+            let amount = number(&mut cursor)?;
+            literal(&mut cursor, " from ")?;
+            let source = stack(&mut cursor)?;
+            literal(&mut cursor, " to ")?;
+            let destination = stack(&mut cursor)?;
+            cursor.end().map_err(|err| Error::InvalidInstruction { span: err.span })?;
+
+            Ok(Instruction { amount, source, destination, span: instruction.span() })
+        })
+        .collect()
+}
+
+fn remove_crates_from_source_stack(
+    stacks: &mut HashMap<u32, LinkedList<char>>,
+    instruction: &Instruction,
+) -> Result<Vec<char>, Error> {
+    let mut crates_to_move = Vec::new();
+
+    if let Some(source_stack) = stacks.get_mut(&instruction.source) {
+        for _ in 0..instruction.amount {
+            match source_stack.pop_back() {
+                Some(c) => crates_to_move.push(c),
+                None => return Err(Error::NotEnoughCrates { span: instruction.span }),
+            }
+        }
+    }
+
+    Ok(crates_to_move)
+}
+
+fn get_top_crate_stack_letters(stacks: &mut HashMap<u32, LinkedList<char>>) -> String {
+    let mut result = String::new();
+
+    // Grab the last element from each stack add it to our result
+    for i in 1..=stacks.keys().len() {
+        if let Some(stack) = stacks.get_mut(&(i as u32)) {
+            if let Some(c) = stack.pop_back() {
+                result.push(c);
+            }
+        }
+    }
+
+    result
+}
+
+/// The crate on top of each stack once a crane that moves one crate at a time is done.
+pub fn part_one(procedure: &Procedure) -> Result<String, Error> {
+    let mut stacks = procedure.stacks.clone();
+
+    for (i, instruction) in procedure.instructions.iter().enumerate() {
+        let removed_crates = remove_crates_from_source_stack(&mut stacks, instruction)?;
+        explain_move(i + 1, instruction, removed_crates.iter());
+
+        if let Some(destination_stack) = stacks.get_mut(&instruction.destination) {
+            // Stack the removed crates onto the destination stack in the order they were removed
+            for c in removed_crates {
+                destination_stack.push_back(c);
+            }
+        }
+    }
+
+    Ok(get_top_crate_stack_letters(&mut stacks))
+}
+
+/// The crate on top of each stack once a crane that moves several crates at once is done.
+pub fn part_two(procedure: &Procedure) -> Result<String, Error> {
+    let mut stacks = procedure.stacks.clone();
+
+    for (i, instruction) in procedure.instructions.iter().enumerate() {
+        let mut removed_crates = remove_crates_from_source_stack(&mut stacks, instruction)?;
+        explain_move(i + 1, instruction, removed_crates.iter().rev());
+
+        if let Some(destination_stack) = stacks.get_mut(&instruction.destination) {
+            // Move through the removed_crates backwards to stack the crates
+            // while keeping the same order from the source stack.
+            while let Some(c) = removed_crates.pop() {
+                destination_stack.push_back(c);
+            }
+        }
+    }
+
+    Ok(get_top_crate_stack_letters(&mut stacks))
+}
+
+// Notes down which crates a move carried, bottom to top as they land
+fn explain_move<'a>(
+    step: usize,
+    instruction: &Instruction,
+    crates: impl Iterator<Item = &'a char>,
+) {
+    explain!(
+        "move",
+        [
+            step = step,
+            crates = crates.collect::<String>(),
+            source = instruction.source,
+            destination = instruction.destination,
+        ],
+        "{step} carries {crates} from {source} to {destination}"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn build_stack_reads_crates_from_the_bottom_up() {
+        let stacks = build_stack(&parse::blocks(EXAMPLE)[0]).unwrap();
+
+        let stack = |number| stacks[&number].iter().collect::<String>();
+        assert_eq!(stacks.len(), 3);
+        assert_eq!(stack(1), "ZN");
+        assert_eq!(stack(2), "MCD");
+        assert_eq!(stack(3), "P");
+    }
+
+    #[test]
+    fn parse_points_at_a_misspelled_instruction() {
+        let input = EXAMPLE.replacen("move 1 from 2 to 1", "move 1 form 2 to 1", 1);
+        match Day5::parse(&input) {
+            Err(Error::InvalidInstruction { span }) => {
+                assert_eq!((span.line, span.column, span.len), (6, 8, 4));
+            }
+            _ => panic!("expected the misspelled instruction to be rejected"),
+        }
+    }
+
+    #[test]
+    fn part_one_example() {
+        let procedure = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part_one(&procedure).unwrap(), Answer::Text(String::from("CMZ")));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let procedure = Day5::parse(EXAMPLE).unwrap();
+        assert_eq!(Day5::part_two(&procedure).unwrap(), Answer::Text(String::from("MCD")));
+    }
+
+    #[test]
+    fn explains_which_crates_each_move_carries() {
+        let procedure = Day5::parse(EXAMPLE).unwrap();
+        explain::enable();
+        part_one(&procedure).unwrap();
+        let one_at_a_time = explain::take();
+        part_two(&procedure).unwrap();
+        let all_at_once = explain::take();
+        explain::disable();
+
+        assert_eq!(one_at_a_time[1].detail, "2 carries DNZ from 1 to 3");
+        assert_eq!(all_at_once[1].detail, "2 carries ZND from 1 to 3");
+        assert_eq!(all_at_once[1].fields[1], ("crates", String::from("ZND")));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day5>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn diagram_parser_survives_fuzzing() {
+        let (diagram, _) = EXAMPLE.split_once("\n\n").unwrap();
+        fuzz::fuzz_parser("build_stack", &[diagram], |text| {
+            build_stack(&parse::lines(text).collect::<Vec<_>>()).map(drop)
+        });
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day5>(stress::generate, stress::brute_force, 100);
+    }
+}
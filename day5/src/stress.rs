@@ -0,0 +1,90 @@
+//! Random crate diagrams, and a brute force solver to check answers against.
+
+use common::{Answer, Rng};
+
+/// A diagram of two to nine stacks followed by `size` moves, each of which has enough crates
+/// on its source stack to be carried out and puts them on a different stack.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut stacks = (0..rng.between(2, 9))
+        .map(|_| {
+            let crates = (0..rng.between(1, 8)).map(|_| (b'A' + rng.below(26) as u8) as char);
+            crates.collect::<Vec<char>>()
+        })
+        .collect::<Vec<_>>();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut input = String::new();
+    for level in (0..height).rev() {
+        let row = stacks.iter().map(|stack| match stack.get(level) {
+            Some(c) => format!("[{}]", c),
+            None => "   ".to_string(),
+        });
+        input += &row.collect::<Vec<_>>().join(" ");
+        input.push('\n');
+    }
+    let numbers = (1..=stacks.len()).map(|n| format!(" {} ", n));
+    input += &numbers.collect::<Vec<_>>().join(" ");
+    input += "\n\n";
+
+    for _ in 0..size {
+        let filled = (0..stacks.len()).filter(|&i| !stacks[i].is_empty()).collect::<Vec<_>>();
+        let source = *rng.pick(&filled);
+        let destination = (source + rng.between(1, stacks.len() - 1)) % stacks.len();
+
+        let height = stacks[source].len();
+        let amount = rng.between(1, height);
+        let moved = stacks[source].split_off(height - amount);
+        stacks[destination].extend(moved);
+        input += &format!("move {} from {} to {}\n", amount, source + 1, destination + 1);
+    }
+    input
+}
+
+/// Reads each stack off its column of the diagram, then moves the crates one by one, or all
+/// at once, as if they were real.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let (diagram, moves) = input.split_once("\n\n").unwrap();
+    let mut rows = diagram.lines().rev();
+    let count = rows.next().unwrap().split_whitespace().count();
+
+    let mut stacks = vec![Vec::new(); count];
+    for row in rows {
+        let row = row.chars().collect::<Vec<char>>();
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            match row.get(1 + 4 * i) {
+                Some(&c) if c != ' ' => stack.push(c),
+                _ => {}
+            }
+        }
+    }
+
+    let moves = moves
+        .lines()
+        .map(|line| {
+            let words = line.split(' ').collect::<Vec<&str>>();
+            let number = |i: usize| words[i].parse::<usize>().unwrap();
+            (number(1), number(3) - 1, number(5) - 1)
+        })
+        .collect::<Vec<_>>();
+
+    let mut one_at_a_time = stacks.clone();
+    for &(amount, source, destination) in &moves {
+        for _ in 0..amount {
+            let c = one_at_a_time[source].pop().unwrap();
+            one_at_a_time[destination].push(c);
+        }
+    }
+
+    let mut all_at_once = stacks;
+    for &(amount, source, destination) in &moves {
+        let height = all_at_once[source].len();
+        let moved = all_at_once[source].split_off(height - amount);
+        all_at_once[destination].extend(moved);
+    }
+
+    let tops = |stacks: Vec<Vec<char>>| {
+        let tops = stacks.iter().filter_map(|stack| stack.last()).collect::<String>();
+        Some(Answer::from(tops))
+    };
+    [tops(one_at_a_time), tops(all_at_once)]
+}
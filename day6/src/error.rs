@@ -0,0 +1,31 @@
+use std::fmt;
+
+use common::{Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 6.
+#[derive(Debug)]
+pub enum Error {
+    NoMarker { span: Span, marker_length: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoMarker { marker_length, .. } => write!(
+                f,
+                "no {} consecutive distinct characters in the datastream",
+                marker_length
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::NoMarker { span, .. } => Some(*span),
+        }
+    }
+}
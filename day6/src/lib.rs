@@ -0,0 +1,139 @@
+//! Day 6: Tuning Trouble.
+//!
+//! Each line is a datastream of letters in which markers of distinct characters are found.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use common::{explain, parse, Answer, Solution, Span};
+
+pub use error::Error;
+
+/// Parses the input into one datastream per line.
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    // Each line of the input is its own datastream
+    type Input = Vec<String>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        Ok(parse::lines(input).map(|datastream| datastream.text.to_string()).collect())
+    }
+
+    fn part_one(datastreams: &Self::Input) -> Result<Answer, Error> {
+        part_one(datastreams).map(Answer::from)
+    }
+
+    fn part_two(datastreams: &Self::Input) -> Result<Answer, Error> {
+        part_two(datastreams).map(Answer::from)
+    }
+}
+
+fn has_duplicate_chars(chars: &[char]) -> bool {
+    for i in 0..chars.len() {
+        for j in (i + 1)..chars.len() {
+            if chars[i] == chars[j] {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Where the first run of `window_size` distinct characters ends, counting from 1, if there
+/// is one.
+pub fn find_marker_start(datastream: &str, window_size: usize) -> Option<usize> {
+    let chars = datastream.chars().collect::<Vec<char>>();
+
+    // The marker is reported as the position just past its last character
+    chars
+        .windows(window_size)
+        .position(|chars| !has_duplicate_chars(chars))
+        .map(|position| position + window_size)
+}
+
+fn sum_marker_starts(datastreams: &[String], window_size: usize) -> Result<usize, Error> {
+    datastreams
+        .iter()
+        .enumerate()
+        .map(|(i, datastream)| {
+            let end = find_marker_start(datastream, window_size).ok_or(Error::NoMarker {
+                span: Span::line(i + 1, datastream),
+                marker_length: window_size,
+            })?;
+            let start = end - window_size;
+            explain!(
+                "datastream",
+                [
+                    datastream = i + 1,
+                    marker = datastream.chars().skip(start).take(window_size).collect::<String>(),
+                    end = end,
+                ],
+                "{datastream} has the marker {marker}, which ends at {end}"
+            );
+            Ok(end)
+        })
+        .sum()
+}
+
+/// The sum over every datastream of where its first start-of-packet marker ends.
+pub fn part_one(datastreams: &[String]) -> Result<usize, Error> {
+    sum_marker_starts(datastreams, 4)
+}
+
+/// The sum over every datastream of where its first start-of-message marker ends.
+pub fn part_two(datastreams: &[String]) -> Result<usize, Error> {
+    sum_marker_starts(datastreams, 14)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn find_marker_start_in_each_datastream() {
+        let datastreams = [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ];
+
+        for (datastream, packet_start, message_start) in datastreams {
+            assert_eq!(find_marker_start(datastream, 4), Some(packet_start));
+            assert_eq!(find_marker_start(datastream, 14), Some(message_start));
+        }
+    }
+
+    #[test]
+    fn part_one_example() {
+        let datastreams = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part_one(&datastreams).unwrap(), Answer::Unsigned(7));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let datastreams = Day6::parse(EXAMPLE).unwrap();
+        assert_eq!(Day6::part_two(&datastreams).unwrap(), Answer::Unsigned(19));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day6>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day6>(stress::generate, stress::brute_force, 1000);
+    }
+}
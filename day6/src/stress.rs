@@ -0,0 +1,32 @@
+//! Random datastreams, and a brute force solver to check answers against.
+
+use std::collections::HashSet;
+
+use common::{Answer, Rng};
+
+/// A single datastream `size` characters long. Only some of the alphabet is used, so that
+/// markers of fourteen distinct characters can turn up late or not at all.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let letters = rng.between(14, 26) as u8;
+    let datastream = (0..size).map(|_| (b'a' + rng.below(letters.into()) as u8) as char);
+    datastream.collect::<String>() + "\n"
+}
+
+fn marker_end(datastream: &[char], length: usize) -> Option<usize> {
+    (length..=datastream.len()).find(|&end| {
+        let window = datastream[end - length..end].iter().collect::<HashSet<_>>();
+        window.len() == length
+    })
+}
+
+/// Puts every window of characters into a set to see whether any of them repeat.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let datastreams = input.lines().map(|line| line.chars().collect::<Vec<char>>());
+    let datastreams = datastreams.collect::<Vec<_>>();
+
+    let total = |length| {
+        let ends = datastreams.iter().map(|datastream| marker_end(datastream, length));
+        ends.sum::<Option<usize>>().map(Answer::from)
+    };
+    [total(4), total(14)]
+}
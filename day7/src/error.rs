@@ -0,0 +1,74 @@
+use std::fmt;
+
+use common::{parse, Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 7.
+#[derive(Debug)]
+pub enum Error {
+    MissingRootChangeDirectory { span: Span },
+    UnknownCommand { span: Span, token: String },
+    InvalidListing { span: Span, token: String },
+    UnknownDirectory { span: Span, token: String },
+    NestedTooDeep { span: Span },
+    /// The files add up to more bytes than fit in a `u64`.
+    SizeOverflow,
+    DiskOverfull { used: u64 },
+    NoDirectoryLargeEnough { needed: u64 },
+    Syntax(parse::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingRootChangeDirectory { .. } => {
+                write!(f, "the terminal output has to start with `$ cd /`")
+            }
+            Error::UnknownCommand { token, .. } => write!(f, "unknown command `{}`", token),
+            Error::InvalidListing { token, .. } => write!(
+                f,
+                "expected `dir <name>` or `<size> <name>`, found `{}`",
+                token
+            ),
+            Error::UnknownDirectory { token, .. } => {
+                write!(f, "no directory named `{}` has been listed here", token)
+            }
+            Error::NestedTooDeep { .. } => write!(
+                f,
+                "directories can only be nested {} deep below `/`",
+                crate::MAX_DEPTH
+            ),
+            Error::SizeOverflow => write!(f, "the files add up to too many bytes to count"),
+            Error::DiskOverfull { used } => {
+                write!(f, "{} bytes are in use, which is more than the disk holds", used)
+            }
+            Error::NoDirectoryLargeEnough { needed } => {
+                write!(f, "no directory frees up the {} bytes needed", needed)
+            }
+            Error::Syntax(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::MissingRootChangeDirectory { span }
+            | Error::UnknownCommand { span, .. }
+            | Error::InvalidListing { span, .. }
+            | Error::UnknownDirectory { span, .. }
+            | Error::NestedTooDeep { span } => Some(*span),
+            Error::Syntax(err) => err.span(),
+            Error::SizeOverflow
+            | Error::DiskOverfull { .. }
+            | Error::NoDirectoryLargeEnough { .. } => None,
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err: parse::Error) -> Self {
+        Error::Syntax(err)
+    }
+}
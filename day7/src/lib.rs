@@ -0,0 +1,389 @@
+//! Day 7: No Space Left On Device.
+//!
+//! A terminal session of `cd` and `ls` commands exploring a filesystem from `/`.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use common::parse::{self, Line};
+use common::{explain, Answer, Solution, Span};
+
+pub use error::Error;
+
+/// How many directories deep the session can go below `/`.
+///
+/// NOTE: Building the filesystem, adding up sizes and dropping it all recurse into each
+/// directory, so a limit keeps a session of thousands of nested `cd`s from overflowing the
+/// stack. Real sessions only go a handful of directories deep.
+pub const MAX_DEPTH: usize = 256;
+
+enum Command<'a> {
+    ChangeDirectory(&'a str),
+    ListCurrentDirectory,
+}
+
+impl<'a> Command<'a> {
+    // Reads a line starting with `$`, or returns `None` for any other line
+    fn parse(line: &Line<'a>) -> Result<Option<Self>, Error> {
+        let mut cursor = line.cursor();
+        if !cursor.eat("$ ") {
+            return Ok(None);
+        }
+
+        let command = match cursor.token() {
+            Ok("ls") => Command::ListCurrentDirectory,
+            Ok("cd") => Command::ChangeDirectory(cursor.token()?),
+            Ok(command) => {
+                return Err(Error::UnknownCommand {
+                    span: line.span_of(command),
+                    token: command.to_string(),
+                })
+            }
+            Err(err) => return Err(Error::UnknownCommand { span: err.span, token: err.found }),
+        };
+        cursor.skip_whitespace();
+        cursor.end()?;
+
+        Ok(Some(command))
+    }
+}
+
+/// An entry in the filesystem. Sizes are only given for files, a folder is as large as
+/// everything in it.
+pub enum File {
+    File { name: String, size: u64 },
+    Folder { name: String, files: Vec<File> },
+}
+
+impl File {
+    /// Reads one line of `ls` output, e.g. `dir a` or `14848514 b.txt`.
+    pub fn parse(listing: &Line) -> Result<Self, Error> {
+        let mut cursor = listing.cursor();
+
+        let file = if cursor.eat("dir ") {
+            File::Folder { name: String::from(cursor.token()?), files: Vec::new() }
+        } else if let Ok(size) = cursor.integer::<u64>() {
+            cursor.literal(" ")?;
+            File::File { name: String::from(cursor.token()?), size }
+        } else {
+            return Err(Error::InvalidListing {
+                span: listing.span(),
+                token: listing.text.to_string(),
+            });
+        };
+        cursor.skip_whitespace();
+        cursor.end()?;
+
+        Ok(file)
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            File::File { name, .. } => name,
+            File::Folder { name, .. } => name,
+        }
+    }
+
+    // NOTE: Parsing checks that the root directory's size fits in a `u64`, and nothing in it
+    // can be larger, so this adds up sizes without checking.
+    pub fn get_size(&self) -> u64 {
+        match self {
+            File::File { size, .. } => *size,
+            File::Folder { files, .. } => files.iter().map(|x| x.get_size()).sum(),
+        }
+    }
+
+    // The size, or `None` when it's too large for a `u64`
+    fn checked_size(&self) -> Option<u64> {
+        match self {
+            File::File { size, .. } => Some(*size),
+            File::Folder { files, .. } => files
+                .iter()
+                .try_fold(0u64, |total, file| total.checked_add(file.checked_size()?)),
+        }
+    }
+}
+
+/// Parses the input into the root directory, with everything the session found in it.
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    // The root directory, with everything found beneath it
+    type Input = File;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        let mut lines = parse::lines(input);
+
+        match lines.next() {
+            Some(line) => match Command::parse(&line) {
+                Ok(Some(Command::ChangeDirectory("/"))) => {
+                    let mut root_directory = File::Folder {
+                        name: String::from("/"),
+                        files: Vec::new(),
+                    };
+                    build_directory(&mut lines, &mut root_directory, 0)?;
+                    match root_directory.checked_size() {
+                        Some(_) => Ok(root_directory),
+                        None => Err(Error::SizeOverflow),
+                    }
+                }
+                _ => Err(Error::MissingRootChangeDirectory { span: line.span() }),
+            },
+            None => Err(Error::MissingRootChangeDirectory {
+                span: Span::end_of(1, ""),
+            }),
+        }
+    }
+
+    fn part_one(root_directory: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_one(root_directory).into())
+    }
+
+    fn part_two(root_directory: &Self::Input) -> Result<Answer, Error> {
+        part_two(root_directory).map(Answer::from)
+    }
+}
+
+// Reads the listings of a directory `depth` directories below `/`, and of the directories
+// below it, until the session leaves it
+fn build_directory<'a, I>(
+    lines: &mut I,
+    context_folder: &mut File,
+    depth: usize,
+) -> Result<(), Error>
+where
+    I: Iterator<Item = Line<'a>>,
+{
+    if let File::Folder { files, .. } = context_folder {
+        while let Some(line) = lines.next() {
+            // Anything that isn't a command is part of a directory listing
+            match Command::parse(&line)? {
+                Some(Command::ChangeDirectory(dir)) => {
+                    if dir == ".." {
+                        return Ok(());
+                    }
+
+                    let index = files
+                        .iter()
+                        .position(|file| {
+                            matches!(file, File::Folder { .. }) && file.get_name() == dir
+                        })
+                        .ok_or_else(|| Error::UnknownDirectory {
+                            span: line.span_of(dir),
+                            token: dir.to_string(),
+                        })?;
+                    if depth == MAX_DEPTH {
+                        return Err(Error::NestedTooDeep { span: line.span_of(dir) });
+                    }
+                    build_directory(lines, &mut files[index], depth + 1)?;
+                }
+                Some(Command::ListCurrentDirectory) => continue,
+                None => files.push(File::parse(&line)?),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn total_size_of_directories_at_most_100000(directory: &File) -> u64 {
+    let mut sum = 0;
+
+    if let File::Folder { files, .. } = directory {
+        if directory.get_size() <= 100000 {
+            sum += directory.get_size()
+        }
+
+        for file in files.iter() {
+            sum += match file {
+                File::Folder { .. } => total_size_of_directories_at_most_100000(file),
+                _ => 0,
+            };
+        }
+    }
+
+    sum
+}
+
+fn smallest_folder_size_at_least(size: u64, directory: &File) -> Option<u64> {
+    let mut possible_folder_sizes = Vec::new();
+
+    if let File::Folder { files, .. } = directory {
+        if directory.get_size() >= size {
+            possible_folder_sizes.push(Some(directory.get_size()));
+        }
+
+        for file in files.iter() {
+            match file {
+                File::Folder { .. } => {
+                    possible_folder_sizes.push(smallest_folder_size_at_least(size, file))
+                }
+                _ => continue,
+            };
+        }
+    }
+
+    if possible_folder_sizes.is_empty() {
+        return None;
+    }
+
+    possible_folder_sizes.iter().flatten().min().copied()
+}
+
+/// The total size of every directory of at most 100000, counting nested ones again.
+pub fn part_one(root_directory: &File) -> u64 {
+    if explain::is_enabled() {
+        explain_sizes(root_directory, "");
+    }
+    total_size_of_directories_at_most_100000(root_directory)
+}
+
+// Notes the size of every directory from this one down, along with its path
+fn explain_sizes(directory: &File, parent: &str) {
+    if let File::Folder { name, files } = directory {
+        let path = match parent {
+            "" => name.clone(),
+            "/" => format!("/{}", name),
+            parent => format!("{}/{}", parent, name),
+        };
+        explain!("directory", [path = &path, size = directory.get_size()], "{path} holds {size}");
+        for file in files {
+            explain_sizes(file, &path);
+        }
+    }
+}
+
+/// The size of the smallest directory that frees up enough space for the update.
+pub fn part_two(root_directory: &File) -> Result<u64, Error> {
+    const FILESYSTEM_SIZE: u64 = 70000000;
+    const UPDATE_SIZE: u64 = 30000000;
+
+    let used = root_directory.get_size();
+    let current_free_space_available = FILESYSTEM_SIZE
+        .checked_sub(used)
+        .ok_or(Error::DiskOverfull { used })?;
+
+    // If there's already enough space then we don't need to delete anything at all
+    let space_needed = match UPDATE_SIZE.checked_sub(current_free_space_available) {
+        Some(space_needed) if space_needed > 0 => space_needed,
+        _ => return Ok(0),
+    };
+    explain!(
+        "space",
+        [used = used, free = current_free_space_available, needed = space_needed],
+        "used is {used} and {free} is free, so {needed} more is needed"
+    );
+
+    smallest_folder_size_at_least(space_needed, root_directory)
+        .ok_or(Error::NoDirectoryLargeEnough { needed: space_needed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn get_size_adds_up_nested_folders() {
+        let folder = File::Folder {
+            name: String::from("a"),
+            files: vec![
+                File::File { name: String::from("f"), size: 29116 },
+                File::Folder {
+                    name: String::from("e"),
+                    files: vec![File::File { name: String::from("i"), size: 584 }],
+                },
+            ],
+        };
+
+        assert_eq!(folder.get_size(), 29116 + 584);
+        assert_eq!(Day7::parse(EXAMPLE).unwrap().get_size(), 48381165);
+    }
+
+    #[test]
+    fn part_one_example() {
+        let root_directory = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part_one(&root_directory).unwrap(), Answer::Unsigned(95437));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let root_directory = Day7::parse(EXAMPLE).unwrap();
+        assert_eq!(Day7::part_two(&root_directory).unwrap(), Answer::Unsigned(24933642));
+    }
+
+    #[test]
+    fn part_one_counts_directories_of_exactly_100000() {
+        let root_directory = Day7::parse("$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n100000 b\n").unwrap();
+        // Both `a` and `/` are exactly 100000
+        assert_eq!(part_one(&root_directory), 200000);
+    }
+
+    #[test]
+    fn part_two_deletes_a_directory_of_exactly_the_space_needed() {
+        // 50000000 used leaves 20000000 free, so exactly 10000000 has to go
+        let session = "$ cd /\n$ ls\ndir a\n40000000 b\n$ cd a\n$ ls\n10000000 c\n";
+        assert_eq!(part_two(&Day7::parse(session).unwrap()).unwrap(), 10000000);
+    }
+
+    #[test]
+    fn sizes_can_be_larger_than_32_bits() {
+        let session = "$ cd /\n$ ls\ndir a\n3000000000 b\n$ cd a\n$ ls\n2000000000 c\n";
+        let root_directory = Day7::parse(session).unwrap();
+        assert_eq!(root_directory.get_size(), 5000000000);
+        assert!(matches!(part_two(&root_directory), Err(Error::DiskOverfull { used: 5000000000 })));
+    }
+
+    #[test]
+    fn parse_rejects_files_adding_up_to_more_than_fits() {
+        let session = "$ cd /\n$ ls\ndir a\n1 b\n$ cd a\n$ ls\n18446744073709551615 c\n";
+        assert!(matches!(Day7::parse(session), Err(Error::SizeOverflow)));
+    }
+
+    #[test]
+    fn parse_rejects_directories_nested_too_deep() {
+        let deepest = "$ ls\ndir a\n$ cd a\n".repeat(MAX_DEPTH);
+        assert!(Day7::parse(&format!("$ cd /\n{}$ ls\n1 b\n", deepest)).is_ok());
+
+        let session = format!("$ cd /\n{}", "$ ls\ndir a\n$ cd a\n".repeat(100_000));
+        match Day7::parse(&session) {
+            Err(Error::NestedTooDeep { span }) => {
+                assert_eq!((span.line, span.column), (3 * (MAX_DEPTH + 1) + 1, 6));
+            }
+            _ => panic!("expected the directories to be nested too deep"),
+        }
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day7>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn command_parser_survives_fuzzing() {
+        let commands = EXAMPLE.lines().filter(|line| line.starts_with('$')).collect::<Vec<_>>();
+        fuzz::fuzz_parser("Command::parse", &commands, |text| {
+            parse::lines(text).try_for_each(|line| Command::parse(&line).map(drop))
+        });
+    }
+
+    #[test]
+    fn listing_parser_survives_fuzzing() {
+        let listings = EXAMPLE.lines().filter(|line| !line.starts_with('$')).collect::<Vec<_>>();
+        fuzz::fuzz_parser("File::parse", &listings, |text| {
+            parse::lines(text).try_for_each(|line| File::parse(&line).map(drop))
+        });
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day7>(stress::generate, stress::brute_force, 200);
+    }
+}
@@ -0,0 +1,122 @@
+//! Random terminal sessions, and a brute force solver to check answers against.
+
+use std::collections::BTreeSet;
+
+use common::{Answer, Rng};
+
+const EXTENSIONS: &[&str] = &["", ".txt", ".dat", ".log"];
+
+// A name made of letters, different for every `n`: a, b, ..., z, ba, bb, ...
+fn name(mut n: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'a' + (n % 26) as u8);
+        n /= 26;
+        if n == 0 {
+            break;
+        }
+    }
+    name.iter().rev().map(|&c| c as char).collect()
+}
+
+struct Directory {
+    files: Vec<u64>,
+    children: Vec<usize>,
+}
+
+/// A session exploring `size` directories below `/`, with file sizes scaled so that between
+/// 30000000 and 70000000 of the disk is used and part two usually has something to delete.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut directories = (0..=size)
+        .map(|_| Directory { files: Vec::new(), children: Vec::new() })
+        .collect::<Vec<_>>();
+    for child in 1..=size {
+        let parent = rng.below(child);
+        directories[parent].children.push(child);
+    }
+
+    // Hand out weights first, then scale them to the disk usage we're after
+    for directory in &mut directories {
+        directory.files = (0..rng.below(5)).map(|_| rng.between(1, 300000) as u64).collect();
+    }
+    let weights = directories.iter().flat_map(|directory| &directory.files).sum::<u64>();
+    let used = rng.between(30000000, 70000000) as u64;
+    for file in directories.iter_mut().flat_map(|directory| &mut directory.files) {
+        *file = (*file * used / weights.max(1)).max(1);
+    }
+
+    let mut session = String::from("$ cd /\n");
+    explore(rng, &directories, 0, &mut session);
+    session
+}
+
+fn explore(rng: &mut Rng, directories: &[Directory], current: usize, session: &mut String) {
+    let directory = &directories[current];
+    let mut listing = directory
+        .children
+        .iter()
+        .enumerate()
+        .map(|(i, _)| format!("dir {}\n", name(i)))
+        .collect::<Vec<_>>();
+    for (i, size) in directory.files.iter().enumerate() {
+        let name = name(directory.children.len() + i);
+        listing.push(format!("{} {}{}\n", size, name, rng.pick(EXTENSIONS)));
+    }
+    for i in (1..listing.len()).rev() {
+        listing.swap(i, rng.below(i + 1));
+    }
+
+    session.push_str("$ ls\n");
+    session.extend(listing);
+    for (i, &child) in directory.children.iter().enumerate() {
+        *session += &format!("$ cd {}\n", name(i));
+        explore(rng, directories, child, session);
+        session.push_str("$ cd ..\n");
+    }
+}
+
+/// Notes the full path of every directory and file, then works out each directory's size by
+/// adding up every file whose path starts with the directory's.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let mut path = Vec::new();
+    let mut directories = BTreeSet::from([Vec::new()]);
+    let mut files = Vec::new();
+
+    for line in input.lines() {
+        match line.split(' ').collect::<Vec<&str>>()[..] {
+            ["$", "cd", "/"] => path.clear(),
+            ["$", "cd", ".."] => {
+                path.pop();
+            }
+            ["$", "cd", directory] => {
+                path.push(directory);
+                directories.insert(path.clone());
+            }
+            ["$", "ls"] | ["dir", _] => {}
+            [size, _] => files.push((path.clone(), size.parse::<u64>().unwrap())),
+            _ => panic!("unexpected line {:?}", line),
+        }
+    }
+
+    let sizes = directories
+        .iter()
+        .map(|directory| {
+            let inside = files.iter().filter(|(path, _)| path.starts_with(directory));
+            inside.map(|(_, size)| size).sum::<u64>()
+        })
+        .collect::<Vec<u64>>();
+
+    let small = sizes.iter().filter(|&&size| size <= 100000).sum::<u64>();
+
+    let used = sizes[0];
+    let to_delete = match 70000000u64.checked_sub(used) {
+        None => None,
+        Some(free) if free >= 30000000 => Some(0),
+        Some(free) => {
+            let needed = 30000000 - free;
+            sizes.iter().copied().filter(|&size| size >= needed).min()
+        }
+    };
+
+    [Some(small.into()), to_delete.map(Answer::from)]
+}
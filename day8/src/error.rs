@@ -0,0 +1,35 @@
+use std::fmt;
+
+use common::{Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 8.
+#[derive(Debug)]
+pub enum Error {
+    InvalidHeight { span: Span, token: String },
+    RaggedRow { span: Span, expected: usize, found: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidHeight { token, .. } => {
+                write!(f, "tree heights are single digits, found `{}`", token)
+            }
+            Error::RaggedRow { expected, found, .. } => write!(
+                f,
+                "expected a row of {} trees like the first one, found {}",
+                expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidHeight { span, .. } | Error::RaggedRow { span, .. } => Some(*span),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Day 8: Treetop Tree House.
+//!
+//! A map of the height (`0` to `9`) of each tree in a rectangular forest.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+
+use common::{explain, Answer, Solution, Span};
+use grid::{Direction, Grid, ParseError, Point};
+
+pub use error::Error;
+
+/// Parses the input into a grid of tree heights.
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+
+    // The height of each tree
+    type Input = Grid<u32>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        get_forest(input)
+    }
+
+    fn part_one(forest: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_one(forest).into())
+    }
+
+    fn part_two(forest: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_two(forest).into())
+    }
+}
+
+fn get_forest(input: &str) -> Result<Grid<u32>, Error> {
+    Grid::parse(input, |c| c.to_digit(10).ok_or(c)).map_err(|err| match err {
+        ParseError::Cell { point, error } => Error::InvalidHeight {
+            span: Span {
+                line: point.y + 1,
+                column: point.x + 1,
+                len: 1,
+            },
+            token: error.to_string(),
+        },
+        ParseError::RaggedRow { row, expected, found } => Error::RaggedRow {
+            span: Span {
+                line: row + 1,
+                column: 1,
+                len: found,
+            },
+            expected,
+            found,
+        },
+    })
+}
+
+/// Whether every tree between `point` and some edge of the forest is shorter than it.
+pub fn is_visible(forest: &Grid<u32>, point: Point) -> bool {
+    let current_tree_height = forest[point];
+
+    // A tree is visible from a direction if every tree between it and the edge is shorter.
+    // Trees on the edge have nothing in the way, so they're always visible.
+    Direction::ORTHOGONAL.into_iter().any(|direction| {
+        forest
+            .ray(point, direction)
+            .all(|(_, &tree_height)| tree_height < current_tree_height)
+    })
+}
+
+/// How many trees can be seen from `point` in each direction, up to and including the
+/// first one that's at least as tall, all multiplied together.
+///
+/// NOTE: The score is a `u64`, since a tree in the middle of a forest only 600 trees across
+/// can see far enough to go past what a `u32` holds. Going past a `u64` would take a forest
+/// of billions of trees.
+pub fn get_scenic_score(forest: &Grid<u32>, point: Point) -> u64 {
+    let current_tree_height = forest[point];
+
+    // The scenic score in each direction is how many trees can be seen before the view is
+    // blocked by a tree at least as tall. Trees on the edge score 0 looking outwards, which
+    // makes their total score 0.
+    Direction::ORTHOGONAL
+        .into_iter()
+        .map(|direction| {
+            let mut score = 0u64;
+            for (_, &tree_height) in forest.ray(point, direction) {
+                score += 1;
+
+                if tree_height >= current_tree_height {
+                    break;
+                }
+            }
+            score
+        })
+        .product()
+}
+
+/// How many trees can be seen from outside the forest.
+pub fn part_one(forest: &Grid<u32>) -> usize {
+    (0..forest.height())
+        .map(|y| {
+            let row = (0..forest.width()).map(|x| Point::new(x, y));
+            let visible = row.filter(|&point| is_visible(forest, point)).count();
+            explain!("row", [row = y, visible = visible], "{row} has {visible} trees visible");
+            visible
+        })
+        .sum()
+}
+
+/// The highest scenic score of any tree, see [`get_scenic_score`].
+pub fn part_two(forest: &Grid<u32>) -> u64 {
+    let best = forest
+        .points()
+        .map(|point| (get_scenic_score(forest, point), point))
+        .max_by_key(|&(score, _)| score);
+
+    let Some((score, tree)) = best else {
+        return 0;
+    };
+    explain!("best tree", [x = tree.x, y = tree.y, score = score], "{x},{y} scores {score}");
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn visibility_and_scenic_score() {
+        let forest = Day8::parse(EXAMPLE).unwrap();
+
+        assert!(is_visible(&forest, Point::new(1, 1)));
+        assert!(!is_visible(&forest, Point::new(3, 1)));
+        assert!(is_visible(&forest, Point::new(0, 4)));
+        assert_eq!(get_scenic_score(&forest, Point::new(2, 1)), 4);
+        assert_eq!(get_scenic_score(&forest, Point::new(2, 3)), 8);
+    }
+
+    #[test]
+    fn part_one_example() {
+        let forest = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part_one(&forest).unwrap(), Answer::Unsigned(21));
+    }
+
+    #[test]
+    fn part_two_example() {
+        let forest = Day8::parse(EXAMPLE).unwrap();
+        assert_eq!(Day8::part_two(&forest).unwrap(), Answer::Unsigned(8));
+    }
+
+    #[test]
+    fn part_two_scores_past_32_bits() {
+        // A tall tree in the middle of a flat forest sees 300 trees in each direction
+        let mut rows = vec!["0".repeat(601); 601];
+        rows[300].replace_range(300..301, "9");
+        let forest = Day8::parse(&rows.join("\n")).unwrap();
+
+        assert_eq!(Day8::part_two(&forest).unwrap(), Answer::Unsigned(300u64.pow(4)));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day8>(&[EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day8>(stress::generate, stress::brute_force, 40);
+    }
+}
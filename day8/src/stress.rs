@@ -0,0 +1,54 @@
+//! Random forests, and a brute force solver to check answers against.
+
+use common::{Answer, Rng};
+
+/// A square forest `size` trees across.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut forest = String::new();
+    for _ in 0..size {
+        forest.extend((0..size).map(|_| char::from_digit(rng.below(10) as u32, 10).unwrap()));
+        forest.push('\n');
+    }
+    forest
+}
+
+/// Walks from every tree to each edge of the forest with nothing but indices.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let forest = input
+        .lines()
+        .map(|row| row.chars().map(|c| c.to_digit(10).unwrap()).collect::<Vec<u32>>())
+        .collect::<Vec<_>>();
+
+    let mut visible = 0usize;
+    let mut best_score = 0u64;
+    for row in 0..forest.len() {
+        for column in 0..forest[row].len() {
+            let height = forest[row][column];
+            let views = [
+                (0..column).rev().map(|x| forest[row][x]).collect::<Vec<u32>>(),
+                (column + 1..forest[row].len()).map(|x| forest[row][x]).collect(),
+                (0..row).rev().map(|y| forest[y][column]).collect(),
+                (row + 1..forest.len()).map(|y| forest[y][column]).collect(),
+            ];
+
+            if views.iter().any(|view| view.iter().all(|&tree| tree < height)) {
+                visible += 1;
+            }
+
+            let mut score = 1u64;
+            for view in &views {
+                let mut seen = 0;
+                for &tree in view {
+                    seen += 1;
+                    if tree >= height {
+                        break;
+                    }
+                }
+                score *= seen;
+            }
+            best_score = best_score.max(score);
+        }
+    }
+
+    [Some(visible.into()), Some(best_score.into())]
+}
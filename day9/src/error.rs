@@ -0,0 +1,44 @@
+use std::fmt;
+
+use common::{parse, Diagnostic, Span};
+
+/// Everything that can go wrong parsing or solving day 9.
+#[derive(Debug)]
+pub enum Error {
+    InvalidDirection { span: Span, token: String },
+    InvalidDistance { span: Span, token: String },
+    MissingDistance { span: Span },
+    Syntax(parse::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidDirection { token, .. } => {
+                write!(f, "expected a direction of U, D, L or R, found `{}`", token)
+            }
+            Error::InvalidDistance { token, .. } => write!(f, "invalid distance `{}`", token),
+            Error::MissingDistance { .. } => write!(f, "the movement is missing its distance"),
+            Error::Syntax(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Diagnostic for Error {
+    fn span(&self) -> Option<Span> {
+        match self {
+            Error::InvalidDirection { span, .. }
+            | Error::InvalidDistance { span, .. }
+            | Error::MissingDistance { span } => Some(*span),
+            Error::Syntax(err) => err.span(),
+        }
+    }
+}
+
+impl From<parse::Error> for Error {
+    fn from(err: parse::Error) -> Self {
+        Error::Syntax(err)
+    }
+}
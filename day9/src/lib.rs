@@ -0,0 +1,331 @@
+//! Day 9: Rope Bridge.
+//!
+//! Moves of the head of a rope, one per line, e.g. `R 4`. The rest of the rope follows.
+
+mod error;
+#[cfg(any(test, feature = "stress"))]
+pub mod stress;
+mod visualise;
+
+use std::collections::{HashMap, LinkedList};
+
+use common::parse::{self, Line};
+use common::{explain, Answer, Solution};
+
+pub use error::Error;
+
+/// Moving the head of the rope some number of steps in one direction.
+#[derive(Debug)]
+pub enum Movement {
+    Up(u32),
+    Down(u32),
+    Left(u32),
+    Right(u32),
+}
+
+/// Where a knot is, with `x` increasing to the right and `y` increasing upwards.
+///
+/// NOTE: Each movement can take the head up to `u32::MAX` steps, so a few of them would be
+/// enough to overflow an `i32`. Stepping the head far enough to overflow an `i64` would take
+/// hundreds of years.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Position {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Movement {
+    fn parse(line: &Line) -> Result<Movement, Error> {
+        let mut cursor = line.cursor();
+
+        let direction = cursor
+            .one_of::<fn(u32) -> Movement>(&[
+                ("U", Movement::Up),
+                ("D", Movement::Down),
+                ("L", Movement::Left),
+                ("R", Movement::Right),
+            ])
+            .map_err(|err| Error::InvalidDirection { span: err.span, token: err.found })?;
+
+        cursor.skip_whitespace();
+        let distance = cursor.integer::<u32>().map_err(|err| match err.at_end() {
+            true => Error::MissingDistance { span: err.span },
+            false => Error::InvalidDistance { span: err.span, token: err.found },
+        })?;
+
+        cursor.skip_whitespace();
+        cursor.end()?;
+
+        Ok(direction(distance))
+    }
+}
+
+/// Parses the input into one [`Movement`] per line.
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Movement>;
+
+    type Error = Error;
+
+    fn parse(input: &str) -> Result<Self::Input, Error> {
+        parse::lines(input).map(|line| Movement::parse(&line)).collect()
+    }
+
+    fn part_one(movements: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_one(movements).into())
+    }
+
+    fn part_two(movements: &Self::Input) -> Result<Answer, Error> {
+        Ok(part_two(movements).into())
+    }
+}
+
+fn add_one_to_visited_tail_positions(
+    visisted_tail_positions: &mut HashMap<Position, u32>,
+    tail_position: &Position,
+) {
+    if let Some(position) = visisted_tail_positions.get_mut(tail_position) {
+        *position += 1;
+    } else {
+        visisted_tail_positions.insert(*tail_position, 1);
+    }
+}
+
+fn adjust_following_knot(following_knot: &mut Position, leading_knot: &Position) {
+    let is_more_than_one_above = (leading_knot.y - following_knot.y) > 1;
+    let is_more_than_one_below = (leading_knot.y - following_knot.y) < -1;
+    let is_more_than_one_right = (leading_knot.x - following_knot.x) > 1;
+    let is_more_than_one_left = (leading_knot.x - following_knot.x) < -1;
+
+    // For part one, dx and dy can never both be 2 at the same time,
+    // but this is possible in part two.
+    if is_more_than_one_above && is_more_than_one_right {
+        following_knot.x = leading_knot.x - 1;
+        following_knot.y = leading_knot.y - 1;
+    } else if is_more_than_one_above && is_more_than_one_left {
+        following_knot.x = leading_knot.x + 1;
+        following_knot.y = leading_knot.y - 1;
+    } else if is_more_than_one_below && is_more_than_one_right {
+        following_knot.x = leading_knot.x - 1;
+        following_knot.y = leading_knot.y + 1;
+    } else if is_more_than_one_below && is_more_than_one_left {
+        following_knot.x = leading_knot.x + 1;
+        following_knot.y = leading_knot.y + 1;
+    } else if is_more_than_one_above {
+        following_knot.x = leading_knot.x;
+        following_knot.y = leading_knot.y - 1;
+    } else if is_more_than_one_below {
+        following_knot.x = leading_knot.x;
+        following_knot.y = leading_knot.y + 1;
+    } else if is_more_than_one_left {
+        following_knot.x = leading_knot.x + 1;
+        following_knot.y = leading_knot.y;
+    } else if is_more_than_one_right {
+        following_knot.x = leading_knot.x - 1;
+        following_knot.y = leading_knot.y;
+    }
+}
+
+fn move_rope(
+    move_head_knot: fn(&mut Position),
+    rope: &mut LinkedList<Position>,
+    visited_tail_positions: &mut HashMap<Position, u32>,
+) {
+    let mut previous_knot = None;
+
+    for (i, knot) in rope.iter_mut().enumerate() {
+        if i == 0 {
+            move_head_knot(knot);
+            previous_knot = Some(knot);
+            continue;
+        }
+
+        adjust_following_knot(knot, previous_knot.unwrap());
+        previous_knot = Some(knot);
+    }
+
+    add_one_to_visited_tail_positions(visited_tail_positions, rope.back().unwrap());
+}
+
+/// How many positions the tail of a two knot rope visits.
+pub fn part_one(movements: &[Movement]) -> usize {
+    visited_by_tail(movements, 2)
+}
+
+/// How many positions the tail of a ten knot rope visits.
+pub fn part_two(movements: &[Movement]) -> usize {
+    visited_by_tail(movements, 10)
+}
+
+fn visited_by_tail(movements: &[Movement], knots: usize) -> usize {
+    // The tail has only visited where it starts if the head never moves
+    let mut visited = 1;
+    let mut steps = steps(movements, knots).peekable();
+    while let Some(step) = steps.next() {
+        visited = step.visited;
+        if steps.peek().is_none_or(|next| next.movement != step.movement) {
+            let tail = step.knots.last().copied().unwrap_or(Position { x: 0, y: 0 });
+            explain!(
+                "movement",
+                [movement = step.movement + 1, x = tail.x, y = tail.y, visited = visited],
+                "{movement} leaves the tail at {x},{y}, having visited {visited} positions"
+            );
+        }
+    }
+    visited
+}
+
+/// Where every knot of the rope is after one step of the head.
+#[derive(Debug, Clone)]
+pub struct Step {
+    /// Which movement the step is part of, counting from 0
+    pub movement: usize,
+    /// The knots from the head to the tail
+    pub knots: Vec<Position>,
+    /// How many positions the tail has visited so far
+    pub visited: usize,
+}
+
+/// The rope moving one step at a time. See [`steps`].
+pub struct Steps<'a> {
+    movements: &'a [Movement],
+    movement: usize,
+    taken: u32,
+    rope: LinkedList<Position>,
+    visited_tail_positions: HashMap<Position, u32>,
+}
+
+/// Every step the head of a rope of `knots` knots takes as it follows `movements`.
+pub fn steps(movements: &[Movement], knots: usize) -> Steps<'_> {
+    let start = Position { x: 0, y: 0 };
+    Steps {
+        movements,
+        movement: 0,
+        taken: 0,
+        rope: vec![start; knots.max(1)].into_iter().collect(),
+        visited_tail_positions: HashMap::from([(start, 1)]),
+    }
+}
+
+impl Iterator for Steps<'_> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        loop {
+            let (distance, move_head_knot): (u32, fn(&mut Position)) =
+                match self.movements.get(self.movement)? {
+                    Movement::Up(distance) => (*distance, |head| head.y += 1),
+                    Movement::Down(distance) => (*distance, |head| head.y -= 1),
+                    Movement::Left(distance) => (*distance, |head| head.x -= 1),
+                    Movement::Right(distance) => (*distance, |head| head.x += 1),
+                };
+
+            if self.taken < distance {
+                self.taken += 1;
+                move_rope(move_head_knot, &mut self.rope, &mut self.visited_tail_positions);
+                return Some(Step {
+                    movement: self.movement,
+                    knots: self.rope.iter().copied().collect(),
+                    visited: self.visited_tail_positions.len(),
+                });
+            }
+
+            self.movement += 1;
+            self.taken = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::fuzz;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    const LARGER_EXAMPLE: &str = include_str!("larger_example.txt");
+
+    fn adjusted(following_knot: (i64, i64), leading_knot: (i64, i64)) -> (i64, i64) {
+        let mut following_knot = Position { x: following_knot.0, y: following_knot.1 };
+        let leading_knot = Position { x: leading_knot.0, y: leading_knot.1 };
+        adjust_following_knot(&mut following_knot, &leading_knot);
+        (following_knot.x, following_knot.y)
+    }
+
+    #[test]
+    fn adjust_following_knot_stays_put_while_touching() {
+        assert_eq!(adjusted((0, 0), (0, 0)), (0, 0));
+        assert_eq!(adjusted((0, 0), (1, 0)), (0, 0));
+        assert_eq!(adjusted((0, 0), (1, 1)), (0, 0));
+    }
+
+    #[test]
+    fn adjust_following_knot_follows_in_a_straight_line() {
+        assert_eq!(adjusted((0, 0), (2, 0)), (1, 0));
+        assert_eq!(adjusted((0, 0), (0, -2)), (0, -1));
+    }
+
+    #[test]
+    fn adjust_following_knot_moves_diagonally_to_catch_up() {
+        assert_eq!(adjusted((0, 0), (1, 2)), (1, 1));
+        assert_eq!(adjusted((0, 0), (-2, -1)), (-1, -1));
+        // Only possible with more than two knots, when the knot ahead moved diagonally
+        assert_eq!(adjusted((0, 0), (2, 2)), (1, 1));
+    }
+
+    #[test]
+    fn knots_can_be_further_away_than_32_bits() {
+        assert_eq!(adjusted((3_000_000_000, 0), (3_000_000_002, 1)), (3_000_000_001, 1));
+        assert_eq!(adjusted((0, -3_000_000_000), (0, -3_000_000_002)), (0, -3_000_000_001));
+    }
+
+    #[test]
+    fn part_one_example() {
+        let movements = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part_one(&movements).unwrap(), Answer::Unsigned(13));
+    }
+
+    #[test]
+    fn part_two_examples() {
+        let movements = Day9::parse(EXAMPLE).unwrap();
+        assert_eq!(Day9::part_two(&movements).unwrap(), Answer::Unsigned(1));
+
+        let movements = Day9::parse(LARGER_EXAMPLE).unwrap();
+        assert_eq!(Day9::part_two(&movements).unwrap(), Answer::Unsigned(36));
+    }
+
+    #[test]
+    fn steps_move_the_head_one_position_at_a_time() {
+        let movements = Day9::parse("R 2\nU 1\n").unwrap();
+        let steps = steps(&movements, 3).map(|step| {
+            let knots = step.knots.iter().map(|knot| (knot.x, knot.y)).collect::<Vec<_>>();
+            (step.movement, knots, step.visited)
+        });
+        let expected = vec![
+            (0, vec![(1, 0), (0, 0), (0, 0)], 1),
+            (0, vec![(2, 0), (1, 0), (0, 0)], 1),
+            (1, vec![(2, 1), (1, 0), (0, 0)], 1),
+        ];
+        assert_eq!(steps.collect::<Vec<_>>(), expected);
+
+        assert_eq!(part_one(&[]), 1);
+    }
+
+    #[test]
+    fn parse_rejects_anything_after_the_distance() {
+        assert!(matches!(Day9::parse("R 4\nU 4 2\n"), Err(Error::Syntax(_))));
+        assert!(matches!(Day9::parse("R 4\nU\n"), Err(Error::MissingDistance { .. })));
+    }
+
+    #[test]
+    fn parser_survives_fuzzing() {
+        fuzz::fuzz::<Day9>(&[EXAMPLE, LARGER_EXAMPLE], stress::generate);
+    }
+
+    #[test]
+    fn solver_agrees_with_brute_force() {
+        common::stress::cross_check::<Day9>(stress::generate, stress::brute_force, 200);
+    }
+}
@@ -0,0 +1,56 @@
+//! Random rope moves, and a brute force solver to check answers against.
+
+use std::collections::HashSet;
+
+use common::{Answer, Rng};
+
+/// A list of `size` moves of the head, each up to 20 steps long.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{} {}\n", rng.pick(&["U", "D", "L", "R"]), rng.between(1, 20)))
+        .collect()
+}
+
+// Where the tail of a rope of `knots` knots has been after every step of `moves`
+fn tail_positions(moves: &[((i64, i64), usize)], knots: usize) -> usize {
+    let mut rope = vec![(0i64, 0i64); knots];
+    let mut visited = HashSet::from([(0, 0)]);
+
+    for &((dx, dy), steps) in moves {
+        for _ in 0..steps {
+            rope[0].0 += dx;
+            rope[0].1 += dy;
+
+            // A knot that's no longer touching the one in front takes one step towards it,
+            // diagonally if it has to
+            for i in 1..knots {
+                let (x, y) = (rope[i - 1].0 - rope[i].0, rope[i - 1].1 - rope[i].1);
+                if x.abs() > 1 || y.abs() > 1 {
+                    rope[i].0 += x.signum();
+                    rope[i].1 += y.signum();
+                }
+            }
+            visited.insert(rope[knots - 1]);
+        }
+    }
+    visited.len()
+}
+
+/// Moves every knot one step at a time with the rules straight from the puzzle.
+pub fn brute_force(input: &str) -> [Option<Answer>; 2] {
+    let moves = input
+        .lines()
+        .map(|line| {
+            let (direction, steps) = line.split_once(' ').unwrap();
+            let step = match direction {
+                "U" => (0, 1),
+                "D" => (0, -1),
+                "L" => (-1, 0),
+                _ => (1, 0),
+            };
+            (step, steps.parse().unwrap())
+        })
+        .collect::<Vec<_>>();
+
+    [Some(tail_positions(&moves, 2).into()), Some(tail_positions(&moves, 10).into())]
+}
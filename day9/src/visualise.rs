@@ -0,0 +1,76 @@
+//! The rope moving across the bridge, one step at a time.
+
+use std::collections::HashSet;
+
+use common::visual::{Colour, Frame, Visualise};
+use common::Part;
+
+use crate::{steps, Day9, Error, Position, Step};
+
+// How much of the bridge is shown around the head of the rope
+const WIDTH: usize = 61;
+const HEIGHT: usize = 25;
+
+impl Visualise for Day9 {
+    /// A frame for each step of the head, looking down on the bridge with the head in the
+    /// middle. The tail leaves a trail of `#` behind it.
+    fn frames(
+        movements: &Self::Input,
+        part: Part,
+    ) -> Result<Box<dyn Iterator<Item = Frame> + '_>, Error> {
+        let knots = match part {
+            Part::One => 2,
+            Part::Two => 10,
+        };
+
+        let mut trail = HashSet::from([Position { x: 0, y: 0 }]);
+        let frames = steps(movements, knots).map(move |step| {
+            trail.extend(step.knots.last());
+            draw(&step, movements.len(), &trail)
+        });
+        Ok(Box::new(frames))
+    }
+}
+
+fn draw(step: &Step, movements: usize, trail: &HashSet<Position>) -> Frame {
+    let mut frame = Frame::new(WIDTH, HEIGHT);
+    let head = step.knots[0];
+
+    // Rows go down the screen but `y` goes up
+    let mut draw = |position: Position, c: char, colour: Colour| {
+        let x = usize::try_from(position.x - head.x + WIDTH as i64 / 2);
+        let y = usize::try_from(head.y - position.y + HEIGHT as i64 / 2);
+        if let (Ok(x), Ok(y)) = (x, y) {
+            frame.draw(x, y, c, Some(colour));
+        }
+    };
+
+    for &position in trail {
+        draw(position, '#', Colour::Grey);
+    }
+    draw(Position { x: 0, y: 0 }, 's', Colour::Cyan);
+
+    // As in the puzzle, knots nearer the head cover the ones behind them
+    let tail = step.knots.len() - 1;
+    for (i, &knot) in step.knots.iter().enumerate().rev() {
+        let label = match (i, tail) {
+            (0, _) => 'H',
+            (1, 1) => 'T',
+            (i, _) => char::from_digit(i as u32, 10).unwrap_or('#'),
+        };
+        let colour = match i {
+            0 => Colour::Green,
+            i if i == tail => Colour::Red,
+            _ => Colour::Yellow,
+        };
+        draw(knot, label, colour);
+    }
+
+    frame.caption = format!(
+        "Move {} of {}, the tail has visited {} positions",
+        step.movement + 1,
+        movements,
+        step.visited
+    );
+    frame
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::point::{Direction, Point};
+
+/// A rectangular grid holding a value in every cell, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Why a character map couldn't be turned into a grid. Rows and columns start at 0,
+/// and columns are counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError<E> {
+    /// The function converting characters into cells failed on the character at `point`.
+    Cell { point: Point, error: E },
+    /// A row isn't as wide as the first row.
+    RaggedRow { row: usize, expected: usize, found: usize },
+}
+
+impl<E: fmt::Display> fmt::Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Cell { point, error } => write!(f, "{} at {}", error, point),
+            ParseError::RaggedRow { row, expected, found } => write!(
+                f,
+                "row {} is {} cells wide, expected {} like the first row",
+                row, found, expected
+            ),
+        }
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for ParseError<E> {}
+
+impl<T> Grid<T> {
+    /// Builds a grid from a character map with one row per line, converting each
+    /// character with `cell`. Blank lines at the end are ignored.
+    pub fn parse<E>(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Grid<T>, ParseError<E>> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        let mut lines = input.lines().collect::<Vec<&str>>();
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+
+        for (y, line) in lines.into_iter().enumerate() {
+            let row_start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let value = cell(c).map_err(|error| ParseError::Cell {
+                    point: Point::new(x, y),
+                    error,
+                })?;
+                cells.push(value);
+            }
+
+            let row_width = cells.len() - row_start;
+            match width {
+                Some(width) if width != row_width => {
+                    return Err(ParseError::RaggedRow {
+                        row: y,
+                        expected: width,
+                        found: row_width,
+                    })
+                }
+                Some(_) => (),
+                None => width = Some(row_width),
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// A grid of the given size with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        match self.contains(point) {
+            true => self.cells.get(point.y * self.width + point.x),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        match self.contains(point) {
+            true => self.cells.get_mut(point.y * self.width + point.x),
+            false => None,
+        }
+    }
+
+    /// Every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Every cell of the grid along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The first point, row by row, whose cell matches `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(point, _)| point)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x` from top to bottom, or none if it's past the right edge.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(height)
+    }
+
+    /// The points up, down, left and right of `point` that are on the grid.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &Direction::ORTHOGONAL)
+    }
+
+    /// Like `neighbours`, but including the diagonal neighbours as well.
+    pub fn all_neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours_in(point, &Direction::ALL)
+    }
+
+    fn neighbours_in<'a>(
+        &'a self,
+        point: Point,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions
+            .iter()
+            .filter_map(move |&direction| point.step(direction))
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// The cells from `point` (but not including it) out to the edge of the grid in `direction`.
+    pub fn ray(&self, point: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        let mut current = point;
+        std::iter::from_fn(move || {
+            current = current.step(direction)?;
+            self.get(current).map(|cell| (current, cell))
+        })
+    }
+
+    /// Draws the grid with one line per row, converting each cell with `draw`.
+    pub fn render(&self, mut draw: impl FnMut(&T) -> char) -> String {
+        let mut output = String::with_capacity((self.width + 1) * self.height);
+        for y in 0..self.height {
+            output.extend(self.row(y).iter().map(&mut draw));
+            output.push('\n');
+        }
+        output
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "{} is outside of a {}x{} grid",
+                point, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("{} is outside of a {}x{} grid", point, width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError<char>> {
+        Grid::parse(input, |c| c.to_digit(10).ok_or(c))
+    }
+
+    fn points(points: &[(usize, usize)]) -> Vec<Point> {
+        points.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn parse_reads_one_row_per_line() {
+        let grid = digits("123\n456\n\n  \n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid[Point::new(2, 0)], 3);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.render(|&digit| char::from_digit(digit, 10).unwrap()), "123\n456\n");
+
+        let empty = digits("\n\n").unwrap();
+        assert_eq!((empty.width(), empty.height(), empty.points().count()), (0, 0, 0));
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows_and_bad_cells() {
+        assert_eq!(
+            digits("123\n45\n789\n"),
+            Err(ParseError::RaggedRow { row: 1, expected: 3, found: 2 })
+        );
+        assert_eq!(
+            digits("123\n4567\n"),
+            Err(ParseError::RaggedRow { row: 1, expected: 3, found: 4 })
+        );
+        // A blank line in the middle is a row with nothing in it
+        assert!(matches!(digits("12\n\n34\n"), Err(ParseError::RaggedRow { row: 1, .. })));
+        assert_eq!(
+            digits("12\n3x\n"),
+            Err(ParseError::Cell { point: Point::new(1, 1), error: 'x' })
+        );
+    }
+
+    #[test]
+    fn neighbours_stay_on_the_grid() {
+        let grid = Grid::filled(3, 3, ());
+        let corner = grid.neighbours(Point::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(corner, points(&[(0, 1), (1, 0)]));
+        let edge = grid.neighbours(Point::new(2, 1)).collect::<Vec<_>>();
+        assert_eq!(edge, points(&[(2, 0), (2, 2), (1, 1)]));
+        assert_eq!(grid.neighbours(Point::new(1, 1)).count(), 4);
+
+        let corner = grid.all_neighbours(Point::new(2, 2)).collect::<Vec<_>>();
+        assert_eq!(corner, points(&[(2, 1), (1, 2), (1, 1)]));
+        let edge = grid.all_neighbours(Point::new(1, 0)).collect::<Vec<_>>();
+        assert_eq!(edge, points(&[(1, 1), (0, 0), (2, 0), (0, 1), (2, 1)]));
+        assert_eq!(grid.all_neighbours(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn rays_run_to_the_edge_without_their_start() {
+        let grid = digits("123\n456\n789\n").unwrap();
+        let ray = |x, y, direction| {
+            grid.ray(Point::new(x, y), direction).map(|(_, &cell)| cell).collect::<Vec<_>>()
+        };
+        assert_eq!(ray(0, 1, Direction::Right), vec![5, 6]);
+        assert_eq!(ray(2, 2, Direction::Up), vec![6, 3]);
+        assert_eq!(ray(0, 0, Direction::DownRight), vec![5, 9]);
+        assert_eq!(ray(0, 0, Direction::Left), Vec::<u32>::new());
+        assert_eq!(ray(2, 1, Direction::Right), Vec::<u32>::new());
+
+        let points = grid.ray(Point::new(1, 2), Direction::Up).map(|(point, _)| point);
+        assert_eq!(points.collect::<Vec<_>>(), self::points(&[(1, 1), (1, 0)]));
+    }
+
+    #[test]
+    fn column_reads_down_the_grid() {
+        let grid = digits("123\n456\n789\n").unwrap();
+        assert_eq!(grid.column(0).copied().collect::<Vec<_>>(), vec![1, 4, 7]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6, 9]);
+        assert_eq!(grid.column(3).count(), 0);
+
+        let tall = digits("1\n2\n3\n").unwrap();
+        assert_eq!(tall.column(0).copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,7 @@
+mod dense;
+mod point;
+mod sparse;
+
+pub use dense::{Grid, ParseError};
+pub use point::{Direction, Point};
+pub use sparse::SparseGrid;
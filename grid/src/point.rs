@@ -0,0 +1,105 @@
+use std::fmt;
+
+/// A position on a grid. `x` counts columns from the left and `y` counts rows from the
+/// top, so moving `Up` makes `y` smaller, the same way the rows of a puzzle input read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Point {
+        Point { x, y }
+    }
+
+    /// The point one step away in `direction`, or `None` when that would go past 0.
+    pub fn step(self, direction: Direction) -> Option<Point> {
+        let (dx, dy) = direction.offset();
+        Some(Point {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// The four directions that share an edge with a cell.
+    pub const ORTHOGONAL: [Direction; 4] =
+        [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+    /// All eight directions, including the ones that only share a corner with a cell.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// How far one step in this direction moves along x and y.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_moves_one_cell_in_each_direction() {
+        let point = Point::new(5, 5);
+        let stepped = Direction::ALL.map(|direction| point.step(direction).unwrap());
+        assert_eq!(
+            stepped,
+            [(5, 4), (5, 6), (4, 5), (6, 5), (4, 4), (6, 4), (4, 6), (6, 6)]
+                .map(|(x, y)| Point::new(x, y))
+        );
+    }
+
+    #[test]
+    fn step_stops_at_the_edges_of_usize() {
+        let origin = Point::new(0, 0);
+        for direction in [Direction::Up, Direction::Left, Direction::UpRight, Direction::DownLeft] {
+            assert_eq!(origin.step(direction), None, "{:?}", direction);
+        }
+        assert_eq!(origin.step(Direction::DownRight), Some(Point::new(1, 1)));
+
+        let corner = Point::new(usize::MAX, usize::MAX);
+        assert_eq!(corner.step(Direction::Right), None);
+        assert_eq!(corner.step(Direction::Down), None);
+        let inside = Point::new(usize::MAX - 1, usize::MAX - 1);
+        assert_eq!(corner.step(Direction::UpLeft), Some(inside));
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use crate::point::{Direction, Point};
+
+/// A grid that only stores the cells that have something in them, for grids that are
+/// mostly empty or whose size isn't known up front.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    /// Fills the cell at `point`, returning what was there before.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// Every filled cell along with its point, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(point, cell)| (*point, cell))
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle holding every filled cell.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+
+        Some(points.fold((first, first), |(min, max), point| {
+            (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            )
+        }))
+    }
+
+    /// The filled cells up, down, left and right of `point`.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = (Point, &T)> {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| point.step(direction))
+            .filter_map(|neighbour| self.get(neighbour).map(|cell| (neighbour, cell)))
+    }
+
+    /// Draws the cells between `min` and `max` (inclusive) with one line per row. `draw` is
+    /// given `None` for empty cells.
+    pub fn render_area(
+        &self,
+        min: Point,
+        max: Point,
+        mut draw: impl FnMut(Option<&T>) -> char,
+    ) -> String {
+        let mut output = String::new();
+        for y in min.y..=max.y {
+            output.extend((min.x..=max.x).map(|x| draw(self.get(Point::new(x, y)))));
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Draws every filled cell, cropped to `bounds`.
+    pub fn render(&self, draw: impl FnMut(Option<&T>) -> char) -> String {
+        match self.bounds() {
+            Some((min, max)) => self.render_area(min, max, draw),
+            None => String::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        SparseGrid {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_hold_every_filled_cell() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render(|_| '#'), "");
+
+        grid.insert(Point::new(5, 2), 'a');
+        assert_eq!(grid.bounds(), Some((Point::new(5, 2), Point::new(5, 2))));
+
+        grid.insert(Point::new(3, 4), 'b');
+        grid.insert(Point::new(7, 3), 'c');
+        assert_eq!(grid.bounds(), Some((Point::new(3, 2), Point::new(7, 4))));
+
+        grid.remove(Point::new(7, 3));
+        assert_eq!(grid.bounds(), Some((Point::new(3, 2), Point::new(5, 4))));
+    }
+
+    #[test]
+    fn render_crops_to_the_bounds() {
+        let grid = [(Point::new(10, 0), 'a'), (Point::new(12, 2), 'b'), (Point::new(11, 1), 'c')]
+            .into_iter()
+            .collect::<SparseGrid<_>>();
+        assert_eq!(grid.render(|cell| cell.copied().unwrap_or('.')), "a..\n.c.\n..b\n");
+
+        let area = grid.render_area(Point::new(9, 0), Point::new(10, 1), |cell| match cell {
+            Some(_) => '#',
+            None => '.',
+        });
+        assert_eq!(area, ".#\n..\n");
+    }
+
+    #[test]
+    fn neighbours_are_only_the_filled_cells() {
+        let grid = [(Point::new(0, 1), 1), (Point::new(1, 1), 2), (Point::new(1, 0), 3)]
+            .into_iter()
+            .collect::<SparseGrid<_>>();
+        let neighbours = grid.neighbours(Point::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(neighbours, vec![(Point::new(0, 1), &1), (Point::new(1, 0), &3)]);
+    }
+}